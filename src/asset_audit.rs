@@ -0,0 +1,428 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use walkdir::WalkDir;
+
+use crate::download_manager::{DownloadKind, DownloadManager};
+use crate::folder_note::FolderNotes;
+use crate::looker_ignore::LookerIgnore;
+use crate::naming::NamingRule;
+use crate::record_manager::{ActionType, RecordFileAction, RecordManager, RecordOrganizationPlan};
+use crate::scan_index::ScanIndex;
+
+/// 2_assets/<kind>/[category] の構成チェック
+pub struct AssetAudit;
+
+/// 2_assets 直下の素材の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetKind {
+    Footage,
+    Graphic,
+    Photo,
+    Illust,
+    Bgm,
+    Sfx,
+}
+
+impl AssetKind {
+    pub const ALL: [AssetKind; 6] = [
+        AssetKind::Footage,
+        AssetKind::Graphic,
+        AssetKind::Photo,
+        AssetKind::Illust,
+        AssetKind::Bgm,
+        AssetKind::Sfx,
+    ];
+
+    pub fn folder_name(&self) -> &'static str {
+        match self {
+            AssetKind::Footage => "footage",
+            AssetKind::Graphic => "graphic",
+            AssetKind::Photo => "photo",
+            AssetKind::Illust => "illust",
+            AssetKind::Bgm => "bgm",
+            AssetKind::Sfx => "sfx",
+        }
+    }
+
+    /// ファイルの種類がこのフォルダに合っているか（素材以外のファイルは問わない）
+    fn accepts(&self, file_kind: DownloadKind) -> bool {
+        match Self::for_file(file_kind) {
+            None => true,
+            Some(expected) => match self {
+                AssetKind::Footage => expected == AssetKind::Footage,
+                AssetKind::Graphic | AssetKind::Photo | AssetKind::Illust => {
+                    matches!(expected, AssetKind::Graphic | AssetKind::Photo)
+                }
+                AssetKind::Bgm | AssetKind::Sfx => {
+                    matches!(expected, AssetKind::Bgm | AssetKind::Sfx)
+                }
+            },
+        }
+    }
+
+    /// ファイルの種類に合う素材フォルダ
+    fn for_file(file_kind: DownloadKind) -> Option<Self> {
+        match file_kind {
+            DownloadKind::Video => Some(AssetKind::Footage),
+            DownloadKind::Photo => Some(AssetKind::Photo),
+            DownloadKind::Graphic => Some(AssetKind::Graphic),
+            DownloadKind::Music => Some(AssetKind::Bgm),
+            DownloadKind::SoundEffect => Some(AssetKind::Sfx),
+            _ => None,
+        }
+    }
+}
+
+/// カテゴリが決まらないファイルの置き場所
+pub const UNCATEGORIZED: &str = "_uncategorized";
+
+/// チェックで見つかった問題の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetIssueKind {
+    /// 2_assets 直下や種類フォルダ直下に置かれたファイル
+    LooseFile,
+    /// 種類フォルダに合わないファイル（photo 内の MP3 など）
+    TypeMismatch,
+}
+
+impl AssetIssueKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AssetIssueKind::LooseFile => "カテゴリ外",
+            AssetIssueKind::TypeMismatch => "種類の不一致",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AssetIssue {
+    pub path: PathBuf,
+    pub kind: AssetIssueKind,
+    pub detail: String,
+}
+
+#[derive(Debug)]
+pub struct AssetAuditReport {
+    pub assets_dir: PathBuf,
+    pub files: usize,
+    pub issues: Vec<AssetIssue>,
+    /// 移動のプラン（record の整理と同じ形式で適用する）
+    pub moves: RecordOrganizationPlan,
+}
+
+impl AssetAudit {
+    /// 2_assets を走査して、カテゴリ外のファイルと種類の合わないファイルの移動先を決める
+    ///
+    /// 移動先のカテゴリは、元の場所のカテゴリかファイル名に含まれる既存のカテゴリ名、
+    /// どちらも無ければ `_uncategorized` にする。
+    pub fn plan(root: &Path) -> Result<AssetAuditReport> {
+        let assets_dir = root.join("2_assets");
+        if !assets_dir.exists() {
+            return Err(anyhow!(
+                "2_assets フォルダが見つかりません（--ensure-structure で作成できます）: {}",
+                assets_dir.display()
+            ));
+        }
+
+        let mut report = AssetAuditReport {
+            assets_dir: assets_dir.clone(),
+            files: 0,
+            issues: Vec::new(),
+            moves: RecordOrganizationPlan::new(assets_dir.clone()),
+        };
+        let mut planned_targets: BTreeSet<PathBuf> = BTreeSet::new();
+
+        // 1. 2_assets 直下のファイル
+        let mut loose = RecordManager::scan_record_folder(&assets_dir, &ScanIndex::uncached(root))?;
+        loose.sort_by(|a, b| a.name.cmp(&b.name));
+        for file in loose {
+            if Self::is_ignored(&file.name) {
+                continue;
+            }
+            report.files += 1;
+            let file_kind = DownloadManager::classify(&file.name, &file.extension);
+            let Some(kind) = AssetKind::for_file(file_kind) else {
+                report.issue(
+                    &file.path,
+                    AssetIssueKind::LooseFile,
+                    "素材の種類が分からないためそのままにします",
+                );
+                continue;
+            };
+            let category = Self::infer_category(&assets_dir.join(kind.folder_name()), &file.name);
+            report.issue(
+                &file.path,
+                AssetIssueKind::LooseFile,
+                format!("{} に移動します", Self::label(kind, &category)),
+            );
+            let folder = assets_dir.join(kind.folder_name()).join(category);
+            report.propose(&file.path, &folder, &file.name, &mut planned_targets)?;
+        }
+
+        // 2. 種類フォルダ内のファイル
+        let ignore = LookerIgnore::new(root);
+        for kind in AssetKind::ALL {
+            let kind_dir = assets_dir.join(kind.folder_name());
+            if !kind_dir.is_dir() {
+                continue;
+            }
+
+            let mut files: Vec<PathBuf> = WalkDir::new(&kind_dir)
+                .follow_links(false)
+                .min_depth(1)
+                .into_iter()
+                .filter_entry(|entry| {
+                    !entry.file_name().to_string_lossy().starts_with('.')
+                        && !ignore.is_ignored(entry.path(), entry.file_type().is_dir())
+                })
+                .filter_map(|e| e.ok())
+                .filter(|entry| entry.file_type().is_file())
+                .map(|entry| entry.into_path())
+                .collect();
+            files.sort();
+
+            for path in files {
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                if Self::is_ignored(&name) {
+                    continue;
+                }
+                report.files += 1;
+
+                let Ok(relative) = path.strip_prefix(&kind_dir) else {
+                    continue;
+                };
+                let extension = path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .unwrap_or("")
+                    .to_lowercase();
+                let file_kind = DownloadManager::classify(&name, &extension);
+                let is_loose = relative.components().count() == 1;
+
+                if !kind.accepts(file_kind) {
+                    let Some(expected) = AssetKind::for_file(file_kind) else {
+                        continue;
+                    };
+                    // カテゴリ以下の構成はそのまま、種類フォルダだけ入れ替える
+                    let (category, rest) = if is_loose {
+                        (
+                            Self::infer_category(&assets_dir.join(expected.folder_name()), &name),
+                            PathBuf::from(&name),
+                        )
+                    } else {
+                        let mut components = relative.components();
+                        let category = components
+                            .next()
+                            .map(|c| c.as_os_str().to_string_lossy().to_string())
+                            .unwrap_or_else(|| UNCATEGORIZED.to_string());
+                        (category, components.as_path().to_path_buf())
+                    };
+                    report.issue(
+                        &path,
+                        AssetIssueKind::TypeMismatch,
+                        format!(
+                            "{} には置けない種類です（{} に移動します）",
+                            kind.folder_name(),
+                            Self::label(expected, &category)
+                        ),
+                    );
+                    let target = assets_dir.join(expected.folder_name()).join(&category).join(rest);
+                    let folder = target.parent().map(Path::to_path_buf).unwrap_or_default();
+                    report.propose(&path, &folder, &name, &mut planned_targets)?;
+                } else if is_loose {
+                    let category = Self::infer_category(&kind_dir, &name);
+                    report.issue(
+                        &path,
+                        AssetIssueKind::LooseFile,
+                        format!("{} に移動します", Self::label(kind, &category)),
+                    );
+                    report.propose(&path, &kind_dir.join(&category), &name, &mut planned_targets)?;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// 種類フォルダ内の既存カテゴリのうち、ファイル名の単語と一致するもの
+    fn infer_category(kind_dir: &Path, file_name: &str) -> String {
+        let stem = Path::new(file_name)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let words: Vec<String> = stem
+            .split(|c: char| c.is_whitespace() || matches!(c, '-' | '_' | '.'))
+            .map(NamingRule::normalize_name)
+            .filter(|word| !word.is_empty())
+            .collect();
+
+        let mut categories: Vec<String> = fs::read_dir(kind_dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.path().is_dir())
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .filter(|name| !name.starts_with('.') && name != UNCATEGORIZED)
+                    .collect()
+            })
+            .unwrap_or_default();
+        categories.sort();
+
+        categories
+            .into_iter()
+            .find(|category| {
+                let normalized = NamingRule::normalize_name(category);
+                words.contains(&normalized)
+            })
+            .unwrap_or_else(|| UNCATEGORIZED.to_string())
+    }
+
+    fn is_ignored(name: &str) -> bool {
+        name.starts_with('.')
+            || LookerIgnore::is_system_file(name)
+            || FolderNotes::is_note_file(name)
+    }
+
+    fn label(kind: AssetKind, category: &str) -> String {
+        format!("{}/{}", kind.folder_name(), category)
+    }
+
+    /// 移動を適用する
+    pub fn apply(report: &AssetAuditReport) -> Result<()> {
+        RecordManager::apply_moves(&report.moves)
+    }
+}
+
+impl AssetAuditReport {
+    fn issue(&mut self, path: &Path, kind: AssetIssueKind, detail: impl Into<String>) {
+        self.issues.push(AssetIssue {
+            path: path.to_path_buf(),
+            kind,
+            detail: detail.into(),
+        });
+    }
+
+    fn propose(
+        &mut self,
+        source: &Path,
+        folder: &Path,
+        name: &str,
+        planned_targets: &mut BTreeSet<PathBuf>,
+    ) -> Result<()> {
+        let target = RecordManager::unique_target_path(folder, name, planned_targets)?;
+        self.moves.push_action(RecordFileAction {
+            source: source.to_path_buf(),
+            target,
+            action_type: ActionType::Move,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn touch(path: &Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, b"x").unwrap();
+    }
+
+    fn target_of(report: &AssetAuditReport, source: &Path) -> Option<PathBuf> {
+        report
+            .moves
+            .actions
+            .iter()
+            .find(|action| action.source == source)
+            .map(|action| action.target.clone())
+    }
+
+    #[test]
+    fn plan_moves_loose_and_mismatched_files() {
+        let root = TempDir::new("asset-audit-plan");
+        let assets = root.join("2_assets");
+        fs::create_dir_all(assets.join("photo/travel")).unwrap();
+        fs::create_dir_all(assets.join("graphic/brand")).unwrap();
+
+        // 2_assets 直下: 既存カテゴリに一致する名前と、一致しない名前
+        touch(&assets.join("logo_brand.png"));
+        touch(&assets.join("banner.png"));
+        touch(&assets.join("notes.xyz"));
+        // 種類フォルダ直下（直下のものと同じ名前で移動先がぶつかる）
+        touch(&assets.join("graphic/banner.png"));
+        // 種類の不一致: カテゴリ以下の構成はそのまま bgm へ
+        touch(&assets.join("photo/travel/day1/song.mp3"));
+        // 移動先に同じ名前のファイルが既にある
+        touch(&assets.join("footage/clip.mp4"));
+        touch(&assets.join("footage/_uncategorized/clip.mp4"));
+        // 置き場所が正しいものは触らない
+        touch(&assets.join("photo/travel/beach.jpg"));
+
+        let report = AssetAudit::plan(&root).unwrap();
+
+        let cases = [
+            ("logo_brand.png", Some("graphic/brand/logo_brand.png")),
+            ("banner.png", Some("graphic/_uncategorized/banner.png")),
+            ("notes.xyz", None),
+            ("graphic/banner.png", Some("graphic/_uncategorized/banner-2.png")),
+            ("photo/travel/day1/song.mp3", Some("bgm/travel/day1/song.mp3")),
+            ("footage/clip.mp4", Some("footage/_uncategorized/clip-2.mp4")),
+            ("photo/travel/beach.jpg", None),
+        ];
+        for (source, expected) in cases {
+            assert_eq!(
+                target_of(&report, &assets.join(source)),
+                expected.map(|target| assets.join(target)),
+                "{}",
+                source
+            );
+        }
+        assert_eq!(report.moves.actions.len(), 5);
+
+        let kind_of = |source: &str| {
+            report
+                .issues
+                .iter()
+                .find(|issue| issue.path == assets.join(source))
+                .map(|issue| issue.kind)
+        };
+        assert_eq!(kind_of("notes.xyz"), Some(AssetIssueKind::LooseFile));
+        assert_eq!(kind_of("graphic/banner.png"), Some(AssetIssueKind::LooseFile));
+        assert_eq!(kind_of("photo/travel/day1/song.mp3"), Some(AssetIssueKind::TypeMismatch));
+        assert_eq!(kind_of("photo/travel/beach.jpg"), None);
+    }
+
+    #[test]
+    fn infer_category_matches_existing_words() {
+        let root = TempDir::new("asset-audit-category");
+        fs::create_dir_all(root.join("Travel Photos")).unwrap();
+        fs::create_dir_all(root.join("brand")).unwrap();
+        fs::create_dir_all(root.join(UNCATEGORIZED)).unwrap();
+        fs::create_dir_all(root.join(".cache")).unwrap();
+
+        let cases = [
+            ("Logo-BRAND_v2.png", "brand"),
+            ("ｂｒａｎｄ.png", "brand"),
+            ("brandnew.png", UNCATEGORIZED),
+            ("travel photos.jpg", UNCATEGORIZED),
+            ("cache.png", UNCATEGORIZED),
+            ("uncategorized.png", UNCATEGORIZED),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(AssetAudit::infer_category(&root, name), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn plan_requires_assets_folder() {
+        let root = TempDir::new("asset-audit-missing");
+        assert!(AssetAudit::plan(&root).is_err());
+    }
+}
@@ -0,0 +1,176 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer};
+
+use crate::folder_note::FolderNoteFormat;
+use crate::gallery_link::LinkMode;
+use crate::gallery_manager::{GalleryLayout, GalleryView};
+
+/// `<root>/.looker/config.toml` から読み込む設定
+///
+/// ファイルが無い場合や項目が省略された場合は既定値を使う。
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct LookerConfig {
+    pub gallery: GalleryConfig,
+    pub project: ProjectConfig,
+    pub downloads: DownloadsConfig,
+    pub structure: StructureConfig,
+}
+
+/// `[gallery]` セクション
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct GalleryConfig {
+    /// 5_gallery のエントリ形式
+    pub link_mode: LinkMode,
+    /// 1 つのプロジェクトに複数の成果物があるときに優先する拡張子（先頭ほど優先）
+    pub preferred_extensions: Vec<String>,
+    /// メディア種別ごとに 1 件ずつエントリを作る
+    pub per_kind: bool,
+    /// プロジェクトフォルダ名と成果物名の一致度のしきい値（0.0〜1.0）
+    #[serde(deserialize_with = "deserialize_threshold")]
+    pub match_threshold: f64,
+    /// 5_gallery の並べ方（`flat` / `category`、既定は `category`）
+    pub layout: GalleryLayout,
+    /// 追加のビュー（`year` / `kind`）
+    pub views: Vec<GalleryView>,
+}
+
+impl Default for GalleryConfig {
+    fn default() -> Self {
+        Self {
+            link_mode: LinkMode::default(),
+            preferred_extensions: [
+                "mp4", "mov", "webm", "mkv", "avi", "wmv", "flv", "png", "jpg", "jpeg", "webp",
+                "gif", "bmp", "svg", "psd", "ai", "pdf", "wav", "flac", "mp3", "aac", "ogg",
+            ]
+            .iter()
+            .map(|ext| ext.to_string())
+            .collect(),
+            per_kind: false,
+            match_threshold: 0.8,
+            layout: GalleryLayout::default(),
+            views: Vec::new(),
+        }
+    }
+}
+
+/// `[project]` セクション（`project new` で作るプロジェクトのひな形）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct ProjectConfig {
+    /// プロジェクトフォルダ内に作るフォルダ（`/` 区切りで入れ子も可）
+    pub folders: Vec<String>,
+    /// 中身をそのままコピーするひな形フォルダ（ルートからの相対パス）
+    pub template_dir: Option<PathBuf>,
+}
+
+impl Default for ProjectConfig {
+    fn default() -> Self {
+        Self {
+            folders: ["assets", "render", "docs"]
+                .iter()
+                .map(|folder| folder.to_string())
+                .collect(),
+            template_dir: None,
+        }
+    }
+}
+
+/// `[downloads]` セクション
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct DownloadsConfig {
+    /// この日数以上前のファイルを「古いファイル」として知らせる
+    pub stale_days: u64,
+}
+
+impl Default for DownloadsConfig {
+    fn default() -> Self {
+        Self { stale_days: 30 }
+    }
+}
+
+/// `[structure]` セクション
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct StructureConfig {
+    /// 標準フォルダ構造のプロファイル（`.looker/structures/<name>.tree`、既定は組み込みの `default`）
+    pub profile: Option<String>,
+    /// `--ensure-structure` で標準フォルダに置く説明ファイル（`markdown` / `desktop-ini`、省略すると作らない）
+    pub folder_notes: Option<FolderNoteFormat>,
+}
+
+impl StructureConfig {
+    /// CLI の指定を優先して使うプロファイル名を決める
+    pub fn profile_or<'a>(&'a self, cli: Option<&'a str>) -> &'a str {
+        cli.or(self.profile.as_deref())
+            .unwrap_or(crate::structure_manager::DEFAULT_PROFILE)
+    }
+}
+
+/// 一致度のしきい値が 0.0〜1.0 に入っているか確かめる（CLI の値の検証にも使う）
+pub fn parse_threshold(text: &str) -> std::result::Result<f64, String> {
+    let value: f64 = text
+        .trim()
+        .parse()
+        .map_err(|_| format!("数値ではありません: {text}"))?;
+    check_threshold(value)
+}
+
+fn check_threshold(value: f64) -> std::result::Result<f64, String> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(format!("0.0〜1.0 の値を指定してください: {value}"))
+    }
+}
+
+fn deserialize_threshold<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<f64, D::Error> {
+    check_threshold(f64::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+}
+
+impl LookerConfig {
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(".looker").join("config.toml")
+    }
+
+    /// 設定ファイルを読み込む
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = Self::path(root);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("設定ファイルの読み込みに失敗: {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("設定ファイルの解析に失敗: {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_threshold_must_be_between_zero_and_one() {
+        let config: LookerConfig = toml::from_str("[gallery]\nmatch-threshold = 0.5\n").unwrap();
+        assert_eq!(config.gallery.match_threshold, 0.5);
+        assert!(toml::from_str::<LookerConfig>("[gallery]\nmatch-threshold = 1.5\n").is_err());
+        assert!(toml::from_str::<LookerConfig>("[gallery]\nmatch-threshold = -0.1\n").is_err());
+    }
+
+    #[test]
+    fn parse_threshold_rejects_out_of_range() {
+        assert_eq!(parse_threshold("0.75"), Ok(0.75));
+        assert!(parse_threshold("5").is_err());
+        assert!(parse_threshold("-1").is_err());
+        assert!(parse_threshold("NaN").is_err());
+        assert!(parse_threshold("abc").is_err());
+    }
+}
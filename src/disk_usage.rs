@@ -0,0 +1,362 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Result};
+use chrono::Local;
+use serde::Serialize;
+
+use crate::naming::NamingRule;
+use crate::scan_index::ScanIndex;
+use crate::scanner::{DriveScanner, FileInfo};
+use crate::structure_manager::StructureDefinition;
+use crate::ui::UI;
+
+/// フォルダ・record の月・プロジェクトごとのディスク使用量
+pub struct DiskUsage;
+
+/// 集計するファイルの条件
+#[derive(Debug, Clone, Default)]
+pub struct UsageFilter {
+    /// このフォルダ以下だけ（ルートからの相対パス）
+    pub path: Option<String>,
+    /// 小文字の拡張子（空ならすべて）
+    pub extensions: Vec<String>,
+    pub min_size: Option<u64>,
+    /// 最終更新からこの日数以上経ったファイルだけ
+    pub older_than_days: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UsageReport {
+    pub root: PathBuf,
+    pub files: usize,
+    pub bytes: u64,
+    /// 標準フォルダごと（構造の定義順）
+    pub folders: Vec<UsageRow>,
+    /// record の種別と月ごと
+    pub records: Vec<RecordUsage>,
+    /// プロジェクトごと（大きい順）
+    pub projects: Vec<UsageRow>,
+    /// 大きいファイル（大きい順）
+    pub largest: Vec<LargeFile>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UsageRow {
+    pub name: String,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecordUsage {
+    pub record_type: String,
+    /// `YYYYMM`
+    pub month: String,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LargeFile {
+    pub path: PathBuf,
+    pub bytes: u64,
+    /// 最終更新日（`YYYY-MM-DD`）
+    pub modified: String,
+}
+
+/// record 直下など種別フォルダに入っていないファイルの種別名
+const UNSORTED_RECORD: &str = "(未整理)";
+
+impl DiskUsage {
+    /// 標準フォルダ構造の最上位のフォルダ（`filter.path` があればそこだけ）を走査して集計する
+    pub fn collect(
+        root: &Path,
+        definition: &StructureDefinition,
+        filter: &UsageFilter,
+        top: usize,
+        index: &ScanIndex,
+    ) -> Result<UsageReport> {
+        let scan_dirs: Vec<PathBuf> = match &filter.path {
+            Some(path) => {
+                let dir = root.join(path.trim_matches('/'));
+                if !dir.is_dir() {
+                    bail!("フォルダが見つかりません: {}", dir.display());
+                }
+                vec![dir]
+            }
+            None => definition
+                .entries
+                .iter()
+                .filter(|entry| !entry.path.contains('/'))
+                .map(|entry| root.join(&entry.path))
+                .filter(|dir| dir.is_dir())
+                .collect(),
+        };
+
+        let now = Local::now();
+        let mut files: Vec<(String, FileInfo)> = Vec::new();
+        for dir in scan_dirs {
+            for file in DriveScanner::scan(&dir, index)? {
+                if file.is_dir {
+                    continue;
+                }
+                let relative = file
+                    .path
+                    .strip_prefix(root)
+                    .unwrap_or(&file.path)
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                if filter.matches(&file, now) {
+                    files.push((relative, file));
+                }
+            }
+        }
+
+        let mut folders: Vec<UsageRow> = definition
+            .entries
+            .iter()
+            .map(|entry| UsageRow {
+                name: entry.path.clone(),
+                files: 0,
+                bytes: 0,
+            })
+            .collect();
+        let mut records: BTreeMap<(String, String), (usize, u64)> = BTreeMap::new();
+        let mut projects: BTreeMap<String, (usize, u64)> = BTreeMap::new();
+
+        for (relative, file) in &files {
+            for folder in &mut folders {
+                if relative.starts_with(&format!("{}/", folder.name)) {
+                    folder.files += 1;
+                    folder.bytes += file.size;
+                }
+            }
+            let parts: Vec<&str> = relative.split('/').collect();
+            if let Some(key) = Self::record_key(&parts, file) {
+                let entry = records.entry(key).or_default();
+                entry.0 += 1;
+                entry.1 += file.size;
+            }
+            if let Some(project) = Self::project_key(&parts) {
+                let entry = projects.entry(project).or_default();
+                entry.0 += 1;
+                entry.1 += file.size;
+            }
+        }
+        // 絞り込みで対象外になったフォルダは表示しない
+        folders.retain(|folder| folder.files > 0);
+
+        let mut projects: Vec<UsageRow> = projects
+            .into_iter()
+            .map(|(name, (files, bytes))| UsageRow { name, files, bytes })
+            .collect();
+        projects.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+
+        let mut largest: Vec<&FileInfo> = files.iter().map(|(_, file)| file).collect();
+        largest.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+        let largest = largest
+            .into_iter()
+            .take(top)
+            .map(|file| LargeFile {
+                path: file.path.clone(),
+                bytes: file.size,
+                modified: file.modified.format("%Y-%m-%d").to_string(),
+            })
+            .collect();
+
+        Ok(UsageReport {
+            root: root.to_path_buf(),
+            files: files.len(),
+            bytes: files.iter().map(|(_, file)| file.size).sum(),
+            folders,
+            records: records
+                .into_iter()
+                .map(|((record_type, month), (files, bytes))| RecordUsage {
+                    record_type,
+                    month,
+                    files,
+                    bytes,
+                })
+                .collect(),
+            projects,
+            largest,
+        })
+    }
+
+    /// `0_inbox/record/<種別>/[YYYY/]<YYYYMM>/...` の種別と月（月のフォルダが無ければ更新日の月）
+    fn record_key(parts: &[&str], file: &FileInfo) -> Option<(String, String)> {
+        if parts.len() < 3 || parts[0] != "0_inbox" || parts[1] != "record" {
+            return None;
+        }
+        let record_type = if parts.len() == 3 {
+            UNSORTED_RECORD.to_string()
+        } else {
+            parts[2].to_string()
+        };
+        let is_digits = |part: &str, len: usize| part.len() == len && part.chars().all(|c| c.is_ascii_digit());
+        // 最後の要素はファイル名なので、月のフォルダはそれより手前にあるものだけ
+        let folders = &parts[..parts.len() - 1];
+        let month = match folders.get(3..) {
+            Some([month, ..]) if is_digits(month, 6) => month.to_string(),
+            Some([year, month, ..]) if is_digits(year, 4) && is_digits(month, 6) => month.to_string(),
+            _ => file.modified.format("%Y%m").to_string(),
+        };
+        Some((record_type, month))
+    }
+
+    /// `1_projects/<category>/YYYYMMDD_name`（カテゴリの無いプロジェクトも含む）
+    fn project_key(parts: &[&str]) -> Option<String> {
+        if parts.first() != Some(&"1_projects") || parts.len() < 3 {
+            return None;
+        }
+        if NamingRule::project_folder_name(parts[1]).is_some() {
+            return Some(parts[..2].join("/"));
+        }
+        (parts.len() > 3).then(|| parts[..3].join("/"))
+    }
+}
+
+impl UsageFilter {
+    fn matches(&self, file: &FileInfo, now: chrono::DateTime<Local>) -> bool {
+        if !self.extensions.is_empty() && !self.extensions.contains(&file.extension) {
+            return false;
+        }
+        if self.min_size.is_some_and(|min| file.size < min) {
+            return false;
+        }
+        if let Some(days) = self.older_than_days
+            && (now - file.modified).num_days() < days as i64
+        {
+            return false;
+        }
+        true
+    }
+}
+
+impl UsageReport {
+    /// Markdown の表にする
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# ディスク使用量\n");
+        let _ = writeln!(
+            out,
+            "- ルート: `{}`\n- 合計: {} 件 / {}\n",
+            self.root.display(),
+            self.files,
+            UI::format_size(self.bytes)
+        );
+
+        let _ = writeln!(out, "## 標準フォルダ\n");
+        let _ = writeln!(out, "| フォルダ | ファイル数 | サイズ |\n| --- | ---: | ---: |");
+        for folder in &self.folders {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} |",
+                folder.name,
+                folder.files,
+                UI::format_size(folder.bytes)
+            );
+        }
+
+        if !self.records.is_empty() {
+            let _ = writeln!(out, "\n## record（種別・月）\n");
+            let _ = writeln!(out, "| 種別 | 月 | ファイル数 | サイズ |\n| --- | --- | ---: | ---: |");
+            for record in &self.records {
+                let _ = writeln!(
+                    out,
+                    "| {} | {} | {} | {} |",
+                    record.record_type,
+                    record.month,
+                    record.files,
+                    UI::format_size(record.bytes)
+                );
+            }
+        }
+
+        if !self.projects.is_empty() {
+            let _ = writeln!(out, "\n## プロジェクト\n");
+            let _ = writeln!(out, "| プロジェクト | ファイル数 | サイズ |\n| --- | ---: | ---: |");
+            for project in &self.projects {
+                let _ = writeln!(
+                    out,
+                    "| {} | {} | {} |",
+                    project.name,
+                    project.files,
+                    UI::format_size(project.bytes)
+                );
+            }
+        }
+
+        if !self.largest.is_empty() {
+            let _ = writeln!(out, "\n## 大きいファイル\n");
+            let _ = writeln!(out, "| ファイル | 更新日 | サイズ |\n| --- | --- | ---: |");
+            for file in &self.largest {
+                let _ = writeln!(
+                    out,
+                    "| `{}` | {} | {} |",
+                    file.path.display(),
+                    file.modified,
+                    UI::format_size(file.bytes)
+                );
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn file(modified: (i32, u32, u32)) -> FileInfo {
+        let (year, month, day) = modified;
+        let modified = Local.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap();
+        FileInfo::new(PathBuf::from("x"), 1, Some(modified.into()), false)
+    }
+
+    #[test]
+    fn record_key_uses_the_month_folder_or_mtime() {
+        let cases: [(&str, Option<(&str, &str)>); 8] = [
+            ("0_inbox/record/screen-capture/202403/a.png", Some(("screen-capture", "202403"))),
+            ("0_inbox/record/screen-capture/2023/202312/a.png", Some(("screen-capture", "202312"))),
+            ("0_inbox/record/screen-capture/202403", Some(("screen-capture", "202301"))),
+            ("0_inbox/record/screen-capture/a.png", Some(("screen-capture", "202301"))),
+            ("0_inbox/record/screen-capture/2024ab/a.png", Some(("screen-capture", "202301"))),
+            ("0_inbox/record/a.png", Some((UNSORTED_RECORD, "202301"))),
+            ("0_inbox/downloads/a.png", None),
+            ("1_projects/film/a.png", None),
+        ];
+        let info = file((2023, 1, 15));
+        for (path, expected) in cases {
+            let parts: Vec<&str> = path.split('/').collect();
+            assert_eq!(
+                DiskUsage::record_key(&parts, &info),
+                expected.map(|(kind, month)| (kind.to_string(), month.to_string())),
+                "{}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn project_key_groups_by_project_folder() {
+        let cases = [
+            ("1_projects/film/20240301_promo/render/a.mp4", Some("1_projects/film/20240301_promo")),
+            ("1_projects/film/20240301_promo/a.mp4", Some("1_projects/film/20240301_promo")),
+            ("1_projects/20240301_promo/a.mp4", Some("1_projects/20240301_promo")),
+            ("1_projects/20240301_My Project/a.mp4", Some("1_projects/20240301_My Project")),
+            ("1_projects/film/a.mp4", None),
+            ("1_projects/a.mp4", None),
+            ("2_assets/film/20240301_promo/a.mp4", None),
+        ];
+        for (path, expected) in cases {
+            let parts: Vec<&str> = path.split('/').collect();
+            assert_eq!(DiskUsage::project_key(&parts), expected.map(str::to_string), "{}", path);
+        }
+    }
+}
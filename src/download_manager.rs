@@ -0,0 +1,246 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use chrono::Local;
+use serde::Serialize;
+
+use crate::looker_ignore::LookerIgnore;
+use crate::record_manager::{
+    ActionType, RecordFileAction, RecordManager, RecordOrganizationPlan, Routed,
+};
+use crate::routing_rules::RoutingRules;
+use crate::scan_index::ScanIndex;
+
+/// 0_inbox/downloads を整理するロジック
+pub struct DownloadManager;
+
+/// ダウンロードしたファイルの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DownloadKind {
+    Installer,
+    Video,
+    Photo,
+    Graphic,
+    Music,
+    SoundEffect,
+    Document,
+    Archive,
+    /// ダウンロード途中のファイル（.crdownload など）
+    Partial,
+    Other,
+}
+
+impl DownloadKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DownloadKind::Installer => "インストーラー",
+            DownloadKind::Video => "動画",
+            DownloadKind::Photo => "写真",
+            DownloadKind::Graphic => "グラフィック",
+            DownloadKind::Music => "音楽",
+            DownloadKind::SoundEffect => "効果音",
+            DownloadKind::Document => "文書",
+            DownloadKind::Archive => "圧縮ファイル",
+            DownloadKind::Partial => "ダウンロード中",
+            DownloadKind::Other => "その他",
+        }
+    }
+
+    /// 移動先（ルートからの相対パス）。移動しない種類は None
+    pub fn destination(&self) -> Option<&'static str> {
+        match self {
+            DownloadKind::Installer => Some("4_apps/_staging"),
+            DownloadKind::Video => Some("2_assets/footage/_uncategorized"),
+            DownloadKind::Photo => Some("2_assets/photo/_uncategorized"),
+            DownloadKind::Graphic => Some("2_assets/graphic/_uncategorized"),
+            DownloadKind::Music => Some("2_assets/bgm/_uncategorized"),
+            DownloadKind::SoundEffect => Some("2_assets/sfx/_uncategorized"),
+            DownloadKind::Document => Some("3_docs/_uncategorized"),
+            DownloadKind::Archive | DownloadKind::Partial | DownloadKind::Other => None,
+        }
+    }
+}
+
+/// 移動せずに知らせるだけのファイル
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadNoteKind {
+    /// zip などの圧縮ファイル（展開するか確認が必要）
+    Archive,
+    /// downloads 内のフォルダ
+    Folder,
+    /// 振り分け先が決まらないファイル
+    Unknown,
+    /// 一定期間以上前のファイル
+    Stale,
+}
+
+impl DownloadNoteKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DownloadNoteKind::Archive => "要確認",
+            DownloadNoteKind::Folder => "フォルダ",
+            DownloadNoteKind::Unknown => "未分類",
+            DownloadNoteKind::Stale => "古いファイル",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DownloadNote {
+    pub path: PathBuf,
+    pub kind: DownloadNoteKind,
+    pub detail: String,
+}
+
+#[derive(Debug)]
+pub struct DownloadPlan {
+    pub downloads_dir: PathBuf,
+    /// 移動のプラン（record の整理と同じ形式で適用する）
+    pub moves: RecordOrganizationPlan,
+    pub notes: Vec<DownloadNote>,
+}
+
+impl DownloadManager {
+    /// downloads 直下のファイルを種類ごとに振り分けるプランを作る
+    ///
+    /// ルールファイルに一致したファイルはルールに従う。
+    /// `stale_days` 日以上前のファイルは移動の有無にかかわらず知らせる。
+    pub fn plan(root: &Path, stale_days: u64, rules: &RoutingRules) -> Result<DownloadPlan> {
+        let downloads_dir = root.join("0_inbox").join("downloads");
+        if !downloads_dir.exists() {
+            return Err(anyhow!(
+                "downloads フォルダが見つかりません（--ensure-structure で作成できます）: {}",
+                downloads_dir.display()
+            ));
+        }
+
+        let mut plan = DownloadPlan {
+            downloads_dir: downloads_dir.clone(),
+            moves: RecordOrganizationPlan::new(downloads_dir.clone()),
+            notes: Vec::new(),
+        };
+        let mut planned_targets: BTreeSet<PathBuf> = BTreeSet::new();
+        let now = Local::now();
+
+        let mut files = RecordManager::scan_record_folder(&downloads_dir, &ScanIndex::uncached(root))?;
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for file in files {
+            if file.name.starts_with('.') || LookerIgnore::is_system_file(&file.name) {
+                continue;
+            }
+
+            let kind = Self::classify(&file.name, &file.extension);
+            if kind == DownloadKind::Partial {
+                // ダウンロード中のファイルには触らない
+                continue;
+            }
+
+            match RecordManager::route_by_rules(&file, rules, &mut planned_targets)? {
+                Routed::AlreadyInPlace => {}
+                Routed::Action(action) => plan.moves.push_action(action),
+                Routed::NoMatch => match kind.destination() {
+                    Some(destination) => {
+                        let target_folder = root.join(destination);
+                        let target = RecordManager::unique_target_path(
+                            &target_folder,
+                            &file.name,
+                            &mut planned_targets,
+                        )?;
+                        plan.moves.push_action(RecordFileAction {
+                            source: file.path.clone(),
+                            target,
+                            action_type: ActionType::Move,
+                        });
+                    }
+                    None => plan.notes.push(if kind == DownloadKind::Archive {
+                        DownloadNote {
+                            path: file.path.clone(),
+                            kind: DownloadNoteKind::Archive,
+                            detail: "展開するか確認してください".to_string(),
+                        }
+                    } else {
+                        DownloadNote {
+                            path: file.path.clone(),
+                            kind: DownloadNoteKind::Unknown,
+                            detail: "振り分け先が決まらないためそのままにします".to_string(),
+                        }
+                    }),
+                }
+            }
+
+            let age_days = (now - file.modified).num_days();
+            if age_days >= stale_days as i64 {
+                plan.notes.push(DownloadNote {
+                    path: file.path.clone(),
+                    kind: DownloadNoteKind::Stale,
+                    detail: format!("{} 日前のファイルです", age_days),
+                });
+            }
+        }
+
+        // サブフォルダは中身が分からないので知らせるだけ
+        let mut folders: Vec<PathBuf> = fs::read_dir(&downloads_dir)
+            .with_context(|| format!("ディレクトリの読み取りに失敗: {:?}", downloads_dir))?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_dir()
+                    && !path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().starts_with('.'))
+                        .unwrap_or(true)
+            })
+            .collect();
+        folders.sort();
+        for folder in folders {
+            plan.notes.push(DownloadNote {
+                path: folder,
+                kind: DownloadNoteKind::Folder,
+                detail: "中身を確認してください".to_string(),
+            });
+        }
+
+        Ok(plan)
+    }
+
+    /// 拡張子（と一部はファイル名）から種類を判定
+    pub fn classify(name: &str, extension: &str) -> DownloadKind {
+        match extension {
+            "exe" | "msi" | "msix" | "msixbundle" | "appx" | "appinstaller" | "dmg" | "pkg"
+            | "deb" | "rpm" | "appimage" | "flatpakref" => DownloadKind::Installer,
+            "mp4" | "mov" | "mkv" | "avi" | "wmv" | "flv" | "webm" | "m4v" => DownloadKind::Video,
+            "jpg" | "jpeg" | "heic" | "heif" | "dng" | "raw" | "cr2" | "cr3" | "nef" | "arw" => {
+                DownloadKind::Photo
+            }
+            "png" | "gif" | "webp" | "svg" | "bmp" | "psd" | "ai" | "eps" | "ico" => {
+                DownloadKind::Graphic
+            }
+            "mp3" | "wav" | "flac" | "aac" | "ogg" | "m4a" | "wma" | "aiff" => {
+                if Self::looks_like_sound_effect(name) {
+                    DownloadKind::SoundEffect
+                } else {
+                    DownloadKind::Music
+                }
+            }
+            "pdf" | "doc" | "docx" | "xls" | "xlsx" | "ppt" | "pptx" | "odt" | "ods" | "odp"
+            | "txt" | "md" | "csv" | "rtf" | "epub" => DownloadKind::Document,
+            "zip" | "rar" | "7z" | "tar" | "gz" | "tgz" | "bz2" | "xz" | "lzh" | "iso" => {
+                DownloadKind::Archive
+            }
+            "crdownload" | "part" | "partial" | "download" | "opdownload" | "tmp" => {
+                DownloadKind::Partial
+            }
+            _ => DownloadKind::Other,
+        }
+    }
+
+    /// 効果音らしい名前か（`sfx` / `se_` / `効果音` を含む）
+    fn looks_like_sound_effect(name: &str) -> bool {
+        let lower = name.to_lowercase();
+        lower.contains("sfx") || lower.starts_with("se_") || lower.contains("_se_") || name.contains("効果音")
+    }
+}
@@ -0,0 +1,299 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::structure_manager::StructureDefinition;
+
+/// 標準フォルダに置く説明ファイル（`_README.md` / `desktop.ini`）
+pub struct FolderNotes;
+
+/// 説明ファイルの形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FolderNoteFormat {
+    /// `_README.md`
+    Markdown,
+    /// Windows のエクスプローラーでヒントに表示される `desktop.ini`
+    DesktopIni,
+}
+
+impl FolderNoteFormat {
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            FolderNoteFormat::Markdown => README_FILE,
+            FolderNoteFormat::DesktopIni => "desktop.ini",
+        }
+    }
+
+    /// 生成したことを示す 1 行目
+    fn marker(&self, hash: &str) -> String {
+        let text = format!("{MARKER}{hash} この行を消すと Looker は更新しません");
+        match self {
+            FolderNoteFormat::Markdown => format!("<!-- {text} -->"),
+            FolderNoteFormat::DesktopIni => format!("; {text}"),
+        }
+    }
+
+    fn body(&self, path: &str, description: &str, profile: &str) -> String {
+        match self {
+            FolderNoteFormat::Markdown => {
+                let name = path.rsplit('/').next().unwrap_or(path);
+                format!(
+                    "# {name}\n\n{description}\n\n`{path}`（標準フォルダ構造: {profile}）\n"
+                )
+            }
+            FolderNoteFormat::DesktopIni => {
+                format!("[.ShellClassInfo]\r\nInfoTip={description}\r\n")
+            }
+        }
+    }
+
+    fn encode(&self, content: &str) -> Vec<u8> {
+        match self {
+            FolderNoteFormat::Markdown => content.as_bytes().to_vec(),
+            // エクスプローラーは BOM 付きの UTF-16LE でないと日本語を読めない
+            FolderNoteFormat::DesktopIni => [0xFF, 0xFE]
+                .into_iter()
+                .chain(content.encode_utf16().flat_map(u16::to_le_bytes))
+                .collect(),
+        }
+    }
+}
+
+/// 説明ファイルの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FolderNoteStatus {
+    Created,
+    Updated,
+    Unchanged,
+    /// 手元で編集されているので書き換えない
+    Edited,
+}
+
+impl FolderNoteStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FolderNoteStatus::Created => "作成",
+            FolderNoteStatus::Updated => "更新",
+            FolderNoteStatus::Unchanged => "変更なし",
+            FolderNoteStatus::Edited => "手元の編集を保持",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderNote {
+    pub path: PathBuf,
+    pub status: FolderNoteStatus,
+}
+
+const README_FILE: &str = "_README.md";
+const MARKER: &str = "looker:generated sha256=";
+
+impl FolderNotes {
+    /// 説明のある既存の標準フォルダに説明ファイルを書く
+    ///
+    /// 生成したときのハッシュを 1 行目に残し、中身がそのハッシュと合わないファイル
+    /// （手元で編集されたもの・Looker 以外が作ったもの）は書き換えない。
+    pub fn sync(
+        root: &Path,
+        definition: &StructureDefinition,
+        format: FolderNoteFormat,
+    ) -> Result<Vec<FolderNote>> {
+        let mut notes = Vec::new();
+
+        for entry in &definition.entries {
+            let Some(description) = &entry.description else {
+                continue;
+            };
+            let folder = root.join(&entry.path);
+            if !folder.is_dir() {
+                continue;
+            }
+
+            let path = folder.join(format.file_name());
+            let body = format.body(&entry.path, description, &definition.profile);
+            let status = match fs::read(&path) {
+                Err(_) => FolderNoteStatus::Created,
+                Ok(bytes) => match Self::generated_body(&Self::decode(&bytes)) {
+                    None => FolderNoteStatus::Edited,
+                    Some(current) if current == body => FolderNoteStatus::Unchanged,
+                    Some(_) => FolderNoteStatus::Updated,
+                },
+            };
+
+            if matches!(status, FolderNoteStatus::Created | FolderNoteStatus::Updated) {
+                let content = format!("{}\n{}", format.marker(&Self::hash(&body)), body);
+                Self::write(&path, &format.encode(&content), format)?;
+            }
+            notes.push(FolderNote { path, status });
+        }
+
+        Ok(notes)
+    }
+
+    /// Looker が作る説明ファイルか（整理やチェックの対象から外す）
+    pub fn is_note_file(name: &str) -> bool {
+        name.eq_ignore_ascii_case(README_FILE) || name.eq_ignore_ascii_case("desktop.ini")
+    }
+
+    /// 生成したままの本文（1 行目のハッシュと合わなければ None）
+    fn generated_body(content: &str) -> Option<String> {
+        let (first, body) = content.split_once('\n')?;
+        let start = first.find(MARKER)? + MARKER.len();
+        let hash: String = first[start..]
+            .chars()
+            .take_while(|c| c.is_ascii_hexdigit())
+            .collect();
+        (hash == Self::hash(body)).then(|| body.to_string())
+    }
+
+    fn hash(body: &str) -> String {
+        Sha256::digest(body.as_bytes())
+            .iter()
+            .take(8)
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    fn decode(bytes: &[u8]) -> String {
+        match bytes {
+            [0xFF, 0xFE, rest @ ..] => {
+                let units: Vec<u16> = rest
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                    .collect();
+                String::from_utf16_lossy(&units)
+            }
+            _ => String::from_utf8_lossy(bytes).to_string(),
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn write(path: &Path, content: &[u8], format: FolderNoteFormat) -> Result<()> {
+        use std::process::Command;
+
+        let attrib = |args: &[&str], target: &Path| {
+            Command::new("attrib").args(args).arg(target).status()
+        };
+        if format == FolderNoteFormat::DesktopIni && path.exists() {
+            // 隠し・システム属性のファイルは上書きできない
+            let _ = attrib(&["-h", "-s"], path);
+        }
+        fs::write(path, content)
+            .with_context(|| format!("説明ファイルの書き込みに失敗: {}", path.display()))?;
+        if format == FolderNoteFormat::DesktopIni {
+            // desktop.ini は読み取り専用のフォルダでだけ読まれる
+            attrib(&["+h", "+s"], path)
+                .and_then(|_| attrib(&["+r"], path.parent().unwrap_or(path)))
+                .with_context(|| format!("属性の設定に失敗: {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn write(path: &Path, content: &[u8], _format: FolderNoteFormat) -> Result<()> {
+        fs::write(path, content)
+            .with_context(|| format!("説明ファイルの書き込みに失敗: {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structure_manager::StructureEntry;
+    use crate::test_support::TempDir;
+
+    fn definition(description: &str) -> StructureDefinition {
+        let entry = |path: &str, description: Option<&str>| StructureEntry {
+            path: path.to_string(),
+            description: description.map(str::to_string),
+        };
+        StructureDefinition {
+            profile: "default".to_string(),
+            entries: vec![
+                entry("0_inbox", Some(description)),
+                entry("1_projects", None),
+                entry("2_assets", Some("素材")),
+            ],
+            templates: Vec::new(),
+        }
+    }
+
+    fn statuses(notes: &[FolderNote]) -> Vec<FolderNoteStatus> {
+        notes.iter().map(|note| note.status).collect()
+    }
+
+    #[test]
+    fn sync_creates_updates_and_keeps_unchanged() {
+        let root = TempDir::new("folder-note-sync");
+        fs::create_dir_all(root.join("0_inbox")).unwrap();
+        fs::create_dir_all(root.join("1_projects")).unwrap();
+        let format = FolderNoteFormat::Markdown;
+
+        // 説明の無いフォルダと存在しないフォルダには書かない
+        let notes = FolderNotes::sync(&root, &definition("受け取り"), format).unwrap();
+        assert_eq!(statuses(&notes), [FolderNoteStatus::Created]);
+        assert_eq!(notes[0].path, root.join("0_inbox/_README.md"));
+        let content = fs::read_to_string(&notes[0].path).unwrap();
+        assert!(content.starts_with("<!-- looker:generated sha256="));
+        assert!(content.contains("受け取り"));
+        assert!(!root.join("1_projects/_README.md").exists());
+
+        let notes = FolderNotes::sync(&root, &definition("受け取り"), format).unwrap();
+        assert_eq!(statuses(&notes), [FolderNoteStatus::Unchanged]);
+
+        let notes = FolderNotes::sync(&root, &definition("一時置き場"), format).unwrap();
+        assert_eq!(statuses(&notes), [FolderNoteStatus::Updated]);
+        assert!(fs::read_to_string(&notes[0].path).unwrap().contains("一時置き場"));
+    }
+
+    #[test]
+    fn sync_keeps_edited_notes() {
+        let root = TempDir::new("folder-note-edited");
+        fs::create_dir_all(root.join("0_inbox")).unwrap();
+        fs::create_dir_all(root.join("2_assets")).unwrap();
+        let format = FolderNoteFormat::Markdown;
+        FolderNotes::sync(&root, &definition("受け取り"), format).unwrap();
+
+        // 本文を書き換えたものと、1 行目のマーカーを消したもの
+        let edited = root.join("0_inbox/_README.md");
+        let mut bytes = fs::read(&edited).unwrap();
+        bytes.extend_from_slice("メモ\n".as_bytes());
+        fs::write(&edited, &bytes).unwrap();
+        let unmarked = root.join("2_assets/_README.md");
+        let content = fs::read_to_string(&unmarked).unwrap();
+        let (_, body) = content.split_once('\n').unwrap();
+        fs::write(&unmarked, body).unwrap();
+
+        let notes = FolderNotes::sync(&root, &definition("一時置き場"), format).unwrap();
+        assert_eq!(statuses(&notes), [FolderNoteStatus::Edited, FolderNoteStatus::Edited]);
+        assert_eq!(fs::read(&edited).unwrap(), bytes);
+        assert_eq!(fs::read_to_string(&unmarked).unwrap(), body);
+    }
+
+    #[test]
+    fn desktop_ini_round_trips_as_utf16() {
+        let format = FolderNoteFormat::DesktopIni;
+        let content = "; 説明\r\n[.ShellClassInfo]\r\nInfoTip=受け取り\r\n";
+        let bytes = format.encode(content);
+        assert_eq!(&bytes[..2], [0xFF, 0xFE]);
+        assert_eq!(bytes.len(), 2 + content.encode_utf16().count() * 2);
+        assert_eq!(FolderNotes::decode(&bytes), content);
+
+        let root = TempDir::new("folder-note-ini");
+        fs::create_dir_all(root.join("0_inbox")).unwrap();
+        let notes = FolderNotes::sync(&root, &definition("受け取り"), format).unwrap();
+        assert_eq!(notes[0].path, root.join("0_inbox/desktop.ini"));
+        let written = FolderNotes::decode(&fs::read(&notes[0].path).unwrap());
+        assert!(written.starts_with("; looker:generated sha256="));
+        assert!(written.ends_with("[.ShellClassInfo]\r\nInfoTip=受け取り\r\n"));
+
+        let notes = FolderNotes::sync(&root, &definition("受け取り"), format).unwrap();
+        assert_eq!(statuses(&notes), [FolderNoteStatus::Unchanged]);
+    }
+}
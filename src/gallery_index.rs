@@ -0,0 +1,157 @@
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chrono::Local;
+use image::{ImageFormat, ImageReader};
+
+use crate::gallery_link::{percent_encode, portable_path, relative_path};
+use crate::gallery_manager::{GalleryItem, GalleryPlan, MediaKind};
+use crate::ui::UI;
+
+/// サムネイルの最大辺（px）
+const THUMBNAIL_SIZE: u32 = 320;
+
+/// 5_gallery/index.html（サーバー不要の一覧ページ）を生成する
+///
+/// サムネイルは PNG/JPEG/GIF/WebP をその場で縮小して data URI として埋め込むため、
+/// index.html 1 ファイルだけで表示できる。それ以外の形式は種別ごとのアイコンを表示する。
+pub struct GalleryIndex;
+
+impl GalleryIndex {
+    pub fn write(plan: &GalleryPlan) -> Result<PathBuf> {
+        let gallery_dir = &plan.gallery_dir;
+        fs::create_dir_all(gallery_dir)
+            .with_context(|| format!("5_gallery の作成に失敗: {}", gallery_dir.display()))?;
+
+        // 新しいプロジェクトから順に並べる
+        let mut items: Vec<&GalleryItem> = plan.items.iter().collect();
+        items.sort_by(|a, b| b.date.cmp(&a.date).then(a.key.cmp(&b.key)));
+
+        let mut cards = String::new();
+        for item in &items {
+            cards.push_str(&Self::card(gallery_dir, item));
+        }
+
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html lang="ja">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>Gallery</title>
+<style>
+body {{ margin: 0; padding: 24px; font-family: system-ui, sans-serif; background: #111; color: #eee; }}
+header {{ margin-bottom: 24px; }}
+header p {{ color: #888; margin: 4px 0 0; }}
+.grid {{ display: grid; grid-template-columns: repeat(auto-fill, minmax(240px, 1fr)); gap: 16px; }}
+.card {{ display: block; background: #1c1c1c; border-radius: 8px; overflow: hidden; color: inherit; text-decoration: none; }}
+.card:hover {{ background: #262626; }}
+.thumb {{ aspect-ratio: 16 / 10; display: flex; align-items: center; justify-content: center; background: #000; font-size: 56px; }}
+.thumb img {{ width: 100%; height: 100%; object-fit: cover; }}
+.meta {{ padding: 10px 12px; }}
+.title {{ font-weight: bold; overflow-wrap: anywhere; }}
+.sub {{ color: #999; font-size: 13px; margin-top: 4px; }}
+</style>
+</head>
+<body>
+<header>
+<h1>Gallery</h1>
+<p>{count} 件 ・ {generated} 生成</p>
+</header>
+<div class="grid">
+{cards}</div>
+</body>
+</html>
+"#,
+            count = items.len(),
+            generated = Local::now().format("%Y-%m-%d %H:%M"),
+            cards = cards,
+        );
+
+        let index_path = gallery_dir.join("index.html");
+        fs::write(&index_path, html)
+            .with_context(|| format!("index.html の書き込みに失敗: {}", index_path.display()))?;
+        Ok(index_path)
+    }
+
+    /// 成果物 1 件分のカード
+    fn card(gallery_dir: &Path, item: &GalleryItem) -> String {
+        let href = percent_encode(&portable_path(&relative_path(gallery_dir, &item.target)));
+        let thumbnail = match Self::thumbnail(&item.target) {
+            Some(data_uri) => format!(r#"<img src="{}" alt="" loading="lazy">"#, data_uri),
+            None => Self::placeholder(item.kind).to_string(),
+        };
+
+        let date = if item.date.len() == 8 {
+            format!("{}-{}-{}", &item.date[..4], &item.date[4..6], &item.date[6..])
+        } else {
+            item.date.clone()
+        };
+        let size = fs::metadata(&item.target)
+            .map(|meta| UI::format_size(meta.len()))
+            .unwrap_or_default();
+        let mut sub = vec![date];
+        if let Some(category) = &item.category {
+            sub.push(category.clone());
+        }
+        sub.push(size);
+        sub.extend(item.tags.iter().map(|tag| format!("#{}", tag)));
+
+        format!(
+            "<a class=\"card\" href=\"{}\">\n<div class=\"thumb\">{}</div>\n<div class=\"meta\"><div class=\"title\">{}</div><div class=\"sub\">{}</div></div>\n</a>\n",
+            escape(&href),
+            thumbnail,
+            escape(&item.title),
+            escape(&sub.join(" ・ ")),
+        )
+    }
+
+    /// 画像を縮小して JPEG の data URI にする（対応外の形式や読めない場合は None）
+    fn thumbnail(path: &Path) -> Option<String> {
+        let format = ImageFormat::from_path(path).ok()?;
+        if !matches!(
+            format,
+            ImageFormat::Png | ImageFormat::Jpeg | ImageFormat::Gif | ImageFormat::WebP
+        ) {
+            return None;
+        }
+
+        let image = ImageReader::open(path)
+            .ok()?
+            .with_guessed_format()
+            .ok()?
+            .decode()
+            .ok()?;
+        let thumbnail = image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgb8();
+
+        let mut buffer = Cursor::new(Vec::new());
+        thumbnail.write_to(&mut buffer, ImageFormat::Jpeg).ok()?;
+        Some(format!(
+            "data:image/jpeg;base64,{}",
+            STANDARD.encode(buffer.into_inner())
+        ))
+    }
+
+    /// サムネイルを作れない形式のアイコン
+    fn placeholder(kind: MediaKind) -> &'static str {
+        match kind {
+            MediaKind::Video => "🎬",
+            MediaKind::Image => "🖼️",
+            MediaKind::Audio => "🎵",
+            MediaKind::Document => "📄",
+        }
+    }
+}
+
+/// HTML 用のエスケープ
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
@@ -0,0 +1,244 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::shell_link::{windows_path, ShellLink};
+
+/// 5_gallery のエントリをどの形式で作るか
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinkMode {
+    /// 絶対パスのシンボリックリンク
+    Symlink,
+    /// 5_gallery からの相対パスのシンボリックリンク（ドライブの付け替えに強い）
+    RelativeSymlink,
+    /// ハードリンク（シンボリックリンクを辿らないメディアプレイヤー向け）
+    Hardlink,
+    /// Windows のショートカット（.lnk）
+    Shortcut,
+    /// freedesktop.org の .desktop ファイル
+    Desktop,
+    /// インターネットショートカット（.url）
+    Url,
+    /// 実ファイルのコピー（FAT/exFAT 向け）
+    Copy,
+}
+
+impl Default for LinkMode {
+    fn default() -> Self {
+        if cfg!(target_os = "windows") {
+            LinkMode::Shortcut
+        } else {
+            LinkMode::RelativeSymlink
+        }
+    }
+}
+
+impl LinkMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LinkMode::Symlink => "symlink",
+            LinkMode::RelativeSymlink => "relative-symlink",
+            LinkMode::Hardlink => "hardlink",
+            LinkMode::Shortcut => "shortcut",
+            LinkMode::Desktop => "desktop",
+            LinkMode::Url => "url",
+            LinkMode::Copy => "copy",
+        }
+    }
+
+    /// エントリのファイル名に付ける拡張子
+    ///
+    /// リンクファイル形式はその形式の拡張子、それ以外はリンク先の拡張子をそのまま使う。
+    pub fn link_extension(&self, target: &Path) -> String {
+        match self {
+            LinkMode::Shortcut => "lnk".to_string(),
+            LinkMode::Desktop => "desktop".to_string(),
+            LinkMode::Url => "url".to_string(),
+            LinkMode::Symlink | LinkMode::RelativeSymlink | LinkMode::Hardlink | LinkMode::Copy => {
+                target
+                    .extension()
+                    .map(|ext| ext.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            }
+        }
+    }
+
+    /// 指定した形式でエントリを作成
+    pub fn create(&self, target: &Path, link_path: &Path) -> Result<()> {
+        match self {
+            LinkMode::Symlink => create_symlink(target, link_path),
+            LinkMode::RelativeSymlink => {
+                let base = link_path.parent().unwrap_or_else(|| Path::new(""));
+                create_symlink(&relative_path(base, target), link_path)
+            }
+            LinkMode::Hardlink => fs::hard_link(target, link_path).with_context(|| {
+                format!(
+                    "ハードリンクの作成に失敗（別ドライブには作成できません）: {:?} -> {:?}",
+                    link_path, target
+                )
+            }),
+            LinkMode::Shortcut => {
+                // ドライブレターが変わっても Explorer が辿れるよう相対パスも埋め込む
+                let mut shortcut = ShellLink::for_target(target);
+                if let Some(base) = link_path.parent() {
+                    let relative = relative_path(base, target);
+                    if relative.is_relative() {
+                        shortcut.relative_path = Some(windows_path(&relative));
+                    }
+                }
+                shortcut.write(link_path)
+            }
+            LinkMode::Desktop => {
+                let name = link_path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let url = file_url(&target.to_string_lossy().replace('\\', "/"));
+                let content = format!("[Desktop Entry]\nType=Link\nName={name}\nURL={url}\n");
+                fs::write(link_path, content)
+                    .with_context(|| format!(".desktop の作成に失敗: {}", link_path.display()))
+            }
+            LinkMode::Url => {
+                let url = file_url(&windows_path(target).replace('\\', "/"));
+                let content = format!("[InternetShortcut]\r\nURL={url}\r\n");
+                fs::write(link_path, content)
+                    .with_context(|| format!(".url の作成に失敗: {}", link_path.display()))
+            }
+            LinkMode::Copy => fs::copy(target, link_path)
+                .map(|_| ())
+                .with_context(|| format!("コピーに失敗: {:?} -> {:?}", target, link_path)),
+        }
+    }
+}
+
+/// 作成済みギャラリーエントリの記録
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GalleryEntry {
+    /// 5_gallery からのエントリの相対パス（`/` 区切り）
+    pub link: String,
+    /// ルートからのリンク先の相対パス（`/` 区切り）
+    pub target: String,
+    /// 作成に使った形式
+    pub mode: LinkMode,
+}
+
+/// 5_gallery に作成したエントリの一覧（`.looker/gallery.json`）
+///
+/// 形式を切り替えたときに古いエントリを見つけて作り直すために使う。
+/// キーは拡張子を除いたエントリ名（例: `20240301_promo`）。
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GalleryManifest {
+    pub entries: BTreeMap<String, GalleryEntry>,
+}
+
+impl GalleryManifest {
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(".looker").join("gallery.json")
+    }
+
+    /// マニフェストを読み込む（無ければ空）
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = Self::path(root);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("ギャラリー記録の読み込みに失敗: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("ギャラリー記録の解析に失敗: {}", path.display()))
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let path = Self::path(root);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("フォルダの作成に失敗: {}", parent.display()))?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&path, content)
+            .with_context(|| format!("ギャラリー記録の書き込みに失敗: {}", path.display()))
+    }
+}
+
+/// `base` ディレクトリから見た `target` の相対パス（パス文字列上で計算）
+///
+/// 別ドライブなど共通の起点が無い場合は `target` をそのまま返す。
+pub fn relative_path(base: &Path, target: &Path) -> PathBuf {
+    let base_parts: Vec<Component> = base.components().collect();
+    let target_parts: Vec<Component> = target.components().collect();
+
+    let common = base_parts
+        .iter()
+        .zip(target_parts.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if common == 0 {
+        return target.to_path_buf();
+    }
+
+    let mut relative = PathBuf::new();
+    for _ in common..base_parts.len() {
+        relative.push("..");
+    }
+    for component in &target_parts[common..] {
+        relative.push(component.as_os_str());
+    }
+    relative
+}
+
+/// OS に依存しない `/` 区切りのパス文字列に変換する
+///
+/// Windows と WSL の両方から同じ記録を読めるようにするため。
+pub fn portable_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// パス文字列を file:// URL に変換する
+fn file_url(path: &str) -> String {
+    if path.starts_with('/') {
+        format!("file://{}", percent_encode(path))
+    } else {
+        format!("file:///{}", percent_encode(path))
+    }
+}
+
+/// URL に使えない文字を %XX に変換する（`/` と `:` はそのまま残す）
+pub fn percent_encode(path: &str) -> String {
+    let mut encoded = String::new();
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' | b':' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+#[cfg(target_os = "windows")]
+pub fn create_symlink(target: &Path, link_path: &Path) -> Result<()> {
+    use std::os::windows::fs::symlink_file;
+
+    symlink_file(target, link_path).with_context(|| {
+        format!(
+            "シンボリックリンクの作成に失敗（開発者モードが必要です）: {:?} -> {:?}",
+            link_path, target
+        )
+    })
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn create_symlink(target: &Path, link_path: &Path) -> Result<()> {
+    use std::os::unix::fs::symlink;
+
+    symlink(target, link_path)
+        .with_context(|| format!("シンボリックリンクの作成に失敗: {:?} -> {:?}", link_path, target))
+}
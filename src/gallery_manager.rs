@@ -1,18 +1,214 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
 
 use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
 use walkdir::WalkDir;
 
+use crate::config::LookerConfig;
+use crate::gallery_link::{
+    create_symlink, portable_path, relative_path, GalleryEntry, GalleryManifest, LinkMode,
+};
+use crate::looker_ignore::LookerIgnore;
+use crate::naming::NamingRule;
+use crate::project_meta::ProjectMeta;
+use crate::shell_link::ShellLink;
 use crate::ui::UI;
 
 /// 1_projects以下のプロジェクト成果物を5_galleryにショートカット作成
 pub struct GalleryManager;
 
+#[derive(Debug, Clone)]
+pub struct GalleryOptions {
+    pub link_mode: LinkMode,
+    /// 候補が複数あるときに優先する拡張子（先頭ほど優先）
+    pub preferred_extensions: Vec<String>,
+    /// メディア種別（動画/画像/音声/文書）ごとに 1 件ずつエントリを作る
+    pub per_kind: bool,
+    /// フォルダ名とファイル名の一致度のしきい値（0.0〜1.0）
+    pub match_threshold: f64,
+    /// 5_gallery 直下の並べ方
+    pub layout: GalleryLayout,
+    /// 追加で作る別の切り口のビュー
+    pub views: Vec<GalleryView>,
+}
+
+/// 5_gallery の並べ方
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GalleryLayout {
+    /// `5_gallery/YYYYMMDD_project` のように直下に並べる
+    Flat,
+    /// `5_gallery/<category>/YYYYMMDD_project` のように 1_projects のカテゴリを再現する
+    #[default]
+    Category,
+}
+
+/// 5_gallery に追加で作るビュー
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GalleryView {
+    /// `5_gallery/_by-year/YYYY/...`
+    Year,
+    /// `5_gallery/_by-kind/{video,image,audio,document}/...`
+    Kind,
+}
+
+impl GalleryView {
+    pub fn folder_name(&self) -> &'static str {
+        match self {
+            GalleryView::Year => "_by-year",
+            GalleryView::Kind => "_by-kind",
+        }
+    }
+}
+
+/// ギャラリー作成の計画
+#[derive(Debug)]
+pub struct GalleryPlan {
+    pub gallery_dir: PathBuf,
+    /// 1_projects 以下で見つかったファイル数
+    pub scanned_files: usize,
+    pub items: Vec<GalleryItem>,
+}
+
+/// ギャラリーに載せる成果物
+#[derive(Debug, Clone)]
+pub struct GalleryItem {
+    /// 拡張子を除いたエントリ名（例: `20240301_promo`、種別ごとなら `20240301_promo.mp4`）
+    pub key: String,
+    /// 表示用のプロジェクト名
+    pub title: String,
+    /// プロジェクトの日付（YYYYMMDD）
+    pub date: String,
+    pub target: PathBuf,
+    pub kind: MediaKind,
+    /// フォルダ名とファイル名の一致度（0.0〜1.0、project.toml で指定したものは 1.0）
+    pub score: f64,
+    /// project.toml で指定したタグ
+    pub tags: Vec<String>,
+    /// 1_projects 直下のカテゴリ名
+    pub category: Option<String>,
+    /// 5_gallery からのエントリの相対パス（拡張子なし、`/` 区切り）
+    ///
+    /// 先頭が並べ方に従った本体で、続いてビューごとのエントリが入る。
+    pub entries: Vec<String>,
+}
+
+/// 成果物のメディア種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MediaKind {
+    Video,
+    Image,
+    Audio,
+    Document,
+}
+
+impl MediaKind {
+    /// ギャラリー対象の拡張子なら種別を返す
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "mp4" | "avi" | "mov" | "mkv" | "wmv" | "flv" | "webm" => Some(MediaKind::Video),
+            "png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" | "psd" | "ai" | "svg" => {
+                Some(MediaKind::Image)
+            }
+            "mp3" | "wav" | "flac" | "aac" | "ogg" => Some(MediaKind::Audio),
+            "pdf" => Some(MediaKind::Document),
+            _ => None,
+        }
+    }
+
+    pub fn folder_name(&self) -> &'static str {
+        match self {
+            MediaKind::Video => "video",
+            MediaKind::Image => "image",
+            MediaKind::Audio => "audio",
+            MediaKind::Document => "document",
+        }
+    }
+}
+
+/// ファイル名末尾の版表記（`_v2`, `_final`）
+///
+/// 表記なし < `_v2` < `_v3` < `_final` の順に新しいものとして扱う。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Revision {
+    Unversioned,
+    Version(u32),
+    Final,
+}
+
+impl Revision {
+    /// `promo_v2` → (`promo`, Version(2)) のように版表記を切り離す
+    pub fn split(name: &str) -> (String, Revision) {
+        let mut base = name;
+        let mut revision = Revision::Unversioned;
+
+        while let Some((head, tail)) = base.rsplit_once('_') {
+            let lower = tail.to_ascii_lowercase();
+            let found = if lower == "final" {
+                Revision::Final
+            } else if let Some(number) = lower.strip_prefix('v')
+                && !number.is_empty()
+                && let Ok(number) = number.parse::<u32>()
+            {
+                Revision::Version(number)
+            } else {
+                break;
+            };
+
+            revision = revision.max(found);
+            base = head;
+        }
+
+        (base.to_string(), revision)
+    }
+}
+
+/// 成果物の候補
+#[derive(Debug, Clone)]
+struct OutputCandidate {
+    path: PathBuf,
+    extension: String,
+    kind: MediaKind,
+    revision: Revision,
+    score: f64,
+    modified: Option<SystemTime>,
+}
+
+/// リンク先が見つからないギャラリーのエントリ
+#[derive(Debug, Clone)]
+pub struct DanglingEntry {
+    pub link: PathBuf,
+    pub target: PathBuf,
+}
+
+/// 絶対パスのシンボリックリンクを相対パスに書き換える計画
+#[derive(Debug, Clone)]
+pub struct LinkMigration {
+    pub link: PathBuf,
+    pub old_target: PathBuf,
+    pub new_target: PathBuf,
+}
+
+impl GalleryOptions {
+    pub fn from_config(config: &LookerConfig) -> Self {
+        Self {
+            link_mode: config.gallery.link_mode,
+            preferred_extensions: config.gallery.preferred_extensions.clone(),
+            per_kind: config.gallery.per_kind,
+            match_threshold: config.gallery.match_threshold,
+            layout: config.gallery.layout,
+            views: config.gallery.views.clone(),
+        }
+    }
+}
+
 impl GalleryManager {
-    /// プロジェクトフォルダを探索してギャラリーショートカットを作成
-    pub fn create_shortcuts(root: &Path) -> Result<()> {
+    /// 1_projects を探索してギャラリーに載せる成果物を決める（ファイルは変更しない）
+    pub fn plan(root: &Path, options: &GalleryOptions) -> Result<GalleryPlan> {
         let projects_dir = root.join("1_projects");
         let gallery_dir = root.join("5_gallery");
 
@@ -20,63 +216,473 @@ impl GalleryManager {
             return Err(anyhow!("1_projects フォルダが見つかりません: {}", projects_dir.display()));
         }
 
+        // 1_projects以下を再帰的に探索
+        let project_files = Self::scan_project_files(root, &projects_dir)?;
+
+        // YYYYMMDD_xxx パターンのプロジェクトフォルダと成果物のマッピング
+        // project.toml / .looker.toml があるプロジェクトは命名規則より設定を優先する
+        let metas = Self::load_project_metas(&project_files)?;
+        let heuristic_files: Vec<PathBuf> = project_files
+            .iter()
+            .filter(|file| match Self::meta_for(&metas, file) {
+                Some((_, meta)) => !meta.exclude && meta.showcase.is_empty(),
+                None => true,
+            })
+            .cloned()
+            .collect();
+
+        let mut items = Self::find_matching_outputs(&projects_dir, &heuristic_files, options)?;
+        let mut used_keys: BTreeSet<String> = items.iter().map(|item| item.key.clone()).collect();
+        items.extend(Self::showcase_items(&projects_dir, &metas, &mut used_keys));
+        for item in &mut items {
+            if let Some((_, meta)) = Self::meta_for(&metas, &item.target) {
+                if let Some(title) = &meta.title {
+                    item.title = title.clone();
+                }
+                item.tags = meta.tags.clone();
+            }
+            item.entries = Self::entry_names(item, options);
+        }
+        items.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Ok(GalleryPlan {
+            gallery_dir,
+            scanned_files: project_files.len(),
+            items,
+        })
+    }
+
+    /// プランに従ってギャラリーのエントリを作成・更新する
+    pub fn apply(root: &Path, plan: &GalleryPlan, options: &GalleryOptions) -> Result<()> {
+        let gallery_dir = &plan.gallery_dir;
+
         // 5_gallery がなければ作成
         if !gallery_dir.exists() {
-            fs::create_dir_all(&gallery_dir)
+            fs::create_dir_all(gallery_dir)
                 .with_context(|| format!("5_gallery の作成に失敗: {}", gallery_dir.display()))?;
             UI::info(&format!("5_gallery フォルダを作成しました: {}", gallery_dir.display()));
         }
 
-        // 1_projects以下を再帰的に探索
-        UI::info("1_projects 以下を探索しています...");
-        let project_files = Self::scan_project_files(&projects_dir)?;
-        
-        if project_files.is_empty() {
-            UI::warning("プロジェクトファイルが見つかりませんでした。");
-            return Ok(());
-        }
+        UI::info(&format!("リンク形式: {}", options.link_mode.label()));
 
-        // YYYYMMDD_xxx パターンのプロジェクトフォルダと成果物のマッピング
-        let matches = Self::find_matching_outputs(&project_files)?;
-        
-        if matches.is_empty() {
-            UI::warning("命名規則に従ったプロジェクト成果物が見つかりませんでした。");
-            return Ok(());
+        let mut manifest = GalleryManifest::load(root)?;
+        // Looker が作ったエントリ（空になったフォルダの片付けに使う）
+        let mut entry_links: Vec<String> =
+            manifest.entries.values().map(|entry| entry.link.clone()).collect();
+
+        // 今回の探索で対象外になったエントリを片付ける
+        let current_names: BTreeSet<&str> = plan
+            .items
+            .iter()
+            .flat_map(|item| item.entries.iter().map(String::as_str))
+            .collect();
+        let stale_names: Vec<String> = manifest
+            .entries
+            .keys()
+            .filter(|name| !current_names.contains(name.as_str()))
+            .cloned()
+            .collect();
+        let mut removed = 0;
+        for name in stale_names {
+            if let Some(entry) = manifest.entries.remove(&name) {
+                let old_path = gallery_dir.join(&entry.link);
+                if old_path.symlink_metadata().is_ok() {
+                    fs::remove_file(&old_path).with_context(|| {
+                        format!("古いエントリの削除に失敗: {}", old_path.display())
+                    })?;
+                }
+                removed += 1;
+                UI::info(&format!("  削除: {}", entry.link));
+            }
         }
+        manifest.save(root)?;
 
-        UI::info(&format!("\n{} 件のプロジェクト成果物を発見しました。", matches.len()));
-        
         // ショートカット作成
         let mut created = 0;
+        let mut converted = 0;
         let mut skipped = 0;
-        
-        for (project_name, target_file) in matches {
-            let shortcut_path = gallery_dir.join(format!("{}.lnk", project_name));
-            
-            // 既に存在する場合はスキップ
-            if shortcut_path.exists() {
-                skipped += 1;
-                continue;
+        let mode = options.link_mode;
+
+        for item in &plan.items {
+            let target_file = &item.target;
+            let target = portable_path(target_file.strip_prefix(root).unwrap_or(target_file));
+            let extension = mode.link_extension(target_file);
+            let links: Vec<String> = item
+                .entries
+                .iter()
+                .map(|name| Self::link_file_name(name, &extension))
+                .collect();
+
+            // 以前のバージョンが 5_gallery 直下に作った `<key>.lnk` は置き換える
+            let legacy_name = format!("{}.lnk", item.key);
+            let legacy_path = gallery_dir.join(&legacy_name);
+            if !links.contains(&legacy_name)
+                && !manifest.entries.values().any(|entry| entry.link == legacy_name)
+                && Self::is_looker_entry(&legacy_path)
+            {
+                fs::remove_file(&legacy_path).with_context(|| {
+                    format!("古いエントリの削除に失敗: {}", legacy_path.display())
+                })?;
+                converted += 1;
+                UI::info(&format!("  旧形式を置き換え: {}", legacy_path.display()));
             }
 
-            // ショートカット作成
-            if Self::create_shortcut(&target_file, &shortcut_path)? {
+            for (name, link) in item.entries.iter().zip(links) {
+                let link_path = gallery_dir.join(&link);
+
+                match manifest.entries.get(name) {
+                    // 同じ形式・同じリンク先で作成済みならスキップ
+                    Some(entry)
+                        if entry.mode == mode
+                            && entry.target == target
+                            && entry.link == link
+                            && link_path.symlink_metadata().is_ok() =>
+                    {
+                        skipped += 1;
+                        continue;
+                    }
+                    // 形式やリンク先が変わったエントリは作り直す
+                    Some(entry) => {
+                        let old_path = gallery_dir.join(&entry.link);
+                        if old_path.symlink_metadata().is_ok() {
+                            fs::remove_file(&old_path).with_context(|| {
+                                format!("古いエントリの削除に失敗: {}", old_path.display())
+                            })?;
+                        }
+                        converted += 1;
+                        UI::info(&format!(
+                            "  変換: {} ({} -> {})",
+                            name,
+                            entry.mode.label(),
+                            mode.label()
+                        ));
+                    }
+                    // 記録の無いエントリ: 以前のバージョンが作ったものだけ置き換える
+                    None => {
+                        if link_path.symlink_metadata().is_ok() {
+                            if !Self::is_looker_entry(&link_path) {
+                                skipped += 1;
+                                continue;
+                            }
+                            fs::remove_file(&link_path).with_context(|| {
+                                format!("古いエントリの削除に失敗: {}", link_path.display())
+                            })?;
+                            UI::info(&format!("  旧形式を置き換え: {}", link_path.display()));
+                        }
+                    }
+                }
+
+                if let Some(parent) = link_path.parent() {
+                    fs::create_dir_all(parent)
+                        .with_context(|| format!("フォルダの作成に失敗: {}", parent.display()))?;
+                }
+                mode.create(target_file, &link_path)?;
                 created += 1;
-                UI::info(&format!("  作成: {} -> {}", project_name, target_file.display()));
+                UI::info(&format!("  作成: {} -> {}", link, target_file.display()));
+
+                entry_links.push(link.clone());
+                manifest
+                    .entries
+                    .insert(name.clone(), GalleryEntry { link, target: target.clone(), mode });
+                manifest.save(root)?;
             }
         }
 
-        UI::success(&format!("\nショートカット作成完了: {} 件作成、{} 件スキップ", created, skipped));
+        // 並べ方の変更などで空になったサブフォルダを片付ける
+        Self::remove_empty_entry_dirs(gallery_dir, &entry_links)?;
+
+        UI::success(&format!(
+            "\nショートカット作成完了: {} 件作成（うち {} 件は形式を変換）、{} 件スキップ、{} 件削除",
+            created, converted, skipped, removed
+        ));
         Ok(())
     }
 
-    /// 1_projects以下のファイルをスキャン
-    fn scan_project_files(projects_dir: &Path) -> Result<Vec<PathBuf>> {
+    /// 指定したフォルダ以下を指すエントリを 5_gallery から削除する
+    ///
+    /// アーカイブなどでプロジェクトを 1_projects から外すときに使う。削除したエントリを返す。
+    pub fn remove_entries_for(root: &Path, project_dir: &Path) -> Result<Vec<String>> {
+        let gallery_dir = root.join("5_gallery");
+        if !gallery_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut manifest = GalleryManifest::load(root)?;
+        let prefix = format!(
+            "{}/",
+            portable_path(project_dir.strip_prefix(root).unwrap_or(project_dir))
+        );
+        let mut removed = Vec::new();
+
+        let names: Vec<String> = manifest
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.target.starts_with(&prefix))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in names {
+            if let Some(entry) = manifest.entries.remove(&name) {
+                let link_path = gallery_dir.join(&entry.link);
+                if link_path.symlink_metadata().is_ok() {
+                    fs::remove_file(&link_path).with_context(|| {
+                        format!("エントリの削除に失敗: {}", link_path.display())
+                    })?;
+                }
+                removed.push(entry.link);
+            }
+        }
+        manifest.save(root)?;
+
+        // 記録の無いシンボリックリンク（以前のバージョンが作ったもの）もリンク先で判定する
+        if let Ok(project_dir) = fs::canonicalize(project_dir) {
+            for entry in WalkDir::new(&gallery_dir)
+                .follow_links(false)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|entry| entry.path_is_symlink())
+            {
+                let points_inside = fs::canonicalize(entry.path())
+                    .map(|target| target.starts_with(&project_dir))
+                    .unwrap_or(false);
+                if points_inside {
+                    fs::remove_file(entry.path()).with_context(|| {
+                        format!("エントリの削除に失敗: {}", entry.path().display())
+                    })?;
+                    let link = entry.path().strip_prefix(&gallery_dir).unwrap_or(entry.path());
+                    removed.push(portable_path(link));
+                }
+            }
+        }
+
+        Self::remove_empty_entry_dirs(&gallery_dir, &removed)?;
+        Ok(removed)
+    }
+
+    /// エントリ名にリンク形式の拡張子を付ける
+    ///
+    /// 種別ごとのエントリ（`promo.mp4`）に同じ拡張子を重ねない。
+    fn link_file_name(name: &str, extension: &str) -> String {
+        if extension.is_empty()
+            || name
+                .to_lowercase()
+                .ends_with(&format!(".{}", extension.to_lowercase()))
+        {
+            name.to_string()
+        } else {
+            format!("{}.{}", name, extension)
+        }
+    }
+
+    /// Looker が作ったエントリの入っていたフォルダのうち、空になったものを削除する
+    ///
+    /// `links` は 5_gallery からのエントリの相対パス。5_gallery 自体と、
+    /// エントリを入れたことの無い（利用者が作った）フォルダは残す。
+    fn remove_empty_entry_dirs(gallery_dir: &Path, links: &[String]) -> Result<()> {
+        let mut dirs: Vec<PathBuf> = links
+            .iter()
+            .flat_map(|link| Path::new(link).ancestors().skip(1))
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map(|dir| gallery_dir.join(dir))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        // 深い階層から順に消す
+        dirs.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+        for dir in dirs {
+            let is_empty = fs::read_dir(&dir)
+                .map(|mut entries| entries.next().is_none())
+                .unwrap_or(false);
+            if is_empty {
+                fs::remove_dir(&dir)
+                    .with_context(|| format!("空フォルダの削除に失敗: {}", dir.display()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// リンク先が無くなった 5_gallery のエントリを探す
+    ///
+    /// シンボリックリンクはリンク先を辿り、それ以外の形式は作成記録のリンク先を確認する。
+    pub fn find_dangling_entries(root: &Path) -> Result<Vec<DanglingEntry>> {
+        let gallery_dir = root.join("5_gallery");
+        if !gallery_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut dangling: BTreeMap<PathBuf, PathBuf> = BTreeMap::new();
+        for entry in WalkDir::new(&gallery_dir)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            if !entry.path_is_symlink() || entry.path().metadata().is_ok() {
+                continue;
+            }
+            let link = entry.into_path();
+            let target = fs::read_link(&link).unwrap_or_default();
+            dangling.insert(link, target);
+        }
+
+        let manifest = GalleryManifest::load(root)?;
+        for entry in manifest.entries.values() {
+            let link = gallery_dir.join(&entry.link);
+            let target = root.join(&entry.target);
+            if link.symlink_metadata().is_ok() && !target.exists() {
+                dangling.entry(link).or_insert(target);
+            }
+        }
+
+        Ok(dangling
+            .into_iter()
+            .map(|(link, target)| DanglingEntry { link, target })
+            .collect())
+    }
+
+    /// 5_gallery 内の絶対パスのシンボリックリンクを探し、相対パスへの書き換えを計画
+    ///
+    /// WSL（`/mnt/d/...`）で作ったリンクを Windows（`D:\...`）から見た場合のように、
+    /// リンク先が現在のルート配下に無くても `1_projects` 以降のパスで読み替える。
+    pub fn plan_relative_link_migration(root: &Path) -> Result<Vec<LinkMigration>> {
+        let gallery_dir = root.join("5_gallery");
+        if !gallery_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let manifest = GalleryManifest::load(root)?;
+        let mut migrations = Vec::new();
+
+        for entry in WalkDir::new(&gallery_dir)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            if !entry.path_is_symlink() {
+                continue;
+            }
+
+            let link = entry.into_path();
+            let old_target = match fs::read_link(&link) {
+                Ok(target) => target,
+                Err(_) => continue,
+            };
+            if !old_target.has_root() {
+                continue;
+            }
+
+            let link_name = portable_path(link.strip_prefix(&gallery_dir).unwrap_or(&link));
+            let recorded = manifest
+                .entries
+                .values()
+                .find(|entry| entry.link == link_name)
+                .map(|entry| PathBuf::from(&entry.target));
+
+            let root_relative = match old_target.strip_prefix(root) {
+                Ok(relative) => Some(relative.to_path_buf()),
+                Err(_) => recorded.or_else(|| Self::projects_relative_path(&old_target)),
+            };
+            let Some(root_relative) = root_relative else {
+                UI::warning(&format!(
+                    "リンク先をルート配下に読み替えられないためスキップ: {} -> {}",
+                    link.display(),
+                    old_target.display()
+                ));
+                continue;
+            };
+
+            let base = link.parent().unwrap_or(&gallery_dir);
+            let new_target = relative_path(base, &root.join(root_relative));
+            migrations.push(LinkMigration {
+                link,
+                old_target,
+                new_target,
+            });
+        }
+
+        migrations.sort_by(|a, b| a.link.cmp(&b.link));
+        Ok(migrations)
+    }
+
+    /// 計画済みのリンク書き換えを適用
+    ///
+    /// 失敗しても残りの処理を続け、最後にサマリを表示する。
+    pub fn migrate_to_relative_links(root: &Path, migrations: &[LinkMigration]) -> Result<()> {
+        let gallery_dir = root.join("5_gallery");
+        let mut manifest = GalleryManifest::load(root)?;
+        let total = migrations.len();
+        let mut migrated = 0usize;
+        let mut failed = 0usize;
+
+        for (idx, migration) in migrations.iter().enumerate() {
+            let result = fs::remove_file(&migration.link)
+                .with_context(|| format!("リンクの削除に失敗: {}", migration.link.display()))
+                .and_then(|_| create_symlink(&migration.new_target, &migration.link));
+
+            match result {
+                Ok(_) => {
+                    migrated += 1;
+                    UI::info(&format!(
+                        "  [{}/{}] {} -> {}",
+                        idx + 1,
+                        total,
+                        migration.link.display(),
+                        migration.new_target.display()
+                    ));
+
+                    let link_name = portable_path(
+                        migration
+                            .link
+                            .strip_prefix(&gallery_dir)
+                            .unwrap_or(&migration.link),
+                    );
+                    for entry in manifest.entries.values_mut() {
+                        if entry.link == link_name && entry.mode == LinkMode::Symlink {
+                            entry.mode = LinkMode::RelativeSymlink;
+                        }
+                    }
+                }
+                Err(e) => {
+                    failed += 1;
+                    UI::warning(&format!(
+                        "  [{}/{}] 失敗: {} ({:#})",
+                        idx + 1,
+                        total,
+                        migration.link.display(),
+                        e
+                    ));
+                }
+            }
+        }
+
+        manifest.save(root)?;
+
+        UI::separator();
+        UI::success(&format!(
+            "処理サマリ: リンク {} 件 (成功 {} / 失敗 {})",
+            total, migrated, failed
+        ));
+        Ok(())
+    }
+
+    /// 絶対パスから `1_projects` 以降の部分を取り出す（区切り文字は `/` と `\` の両方を許容）
+    fn projects_relative_path(target: &Path) -> Option<PathBuf> {
+        let text = target.to_string_lossy();
+        let parts: Vec<&str> = text.split(['/', '\\']).filter(|p| !p.is_empty()).collect();
+        let start = parts.iter().position(|part| *part == "1_projects")?;
+        Some(parts[start..].iter().collect())
+    }
+
+    /// 1_projects以下のファイルをスキャン（`.lookerignore` で除外したものは含まない）
+    fn scan_project_files(root: &Path, projects_dir: &Path) -> Result<Vec<PathBuf>> {
         let mut files = Vec::new();
-        
+        let ignore = LookerIgnore::new(root);
+
         for entry in WalkDir::new(projects_dir)
             .follow_links(false)
             .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0 || !ignore.is_ignored(entry.path(), entry.file_type().is_dir())
+            })
             .filter_map(|e| e.ok())
         {
             if entry.file_type().is_file() {
@@ -87,66 +693,357 @@ impl GalleryManager {
         Ok(files)
     }
 
+    /// 1_projects 以下のプロジェクト設定（project.toml / .looker.toml）を読み込む
+    fn load_project_metas(files: &[PathBuf]) -> Result<BTreeMap<PathBuf, ProjectMeta>> {
+        let mut metas = BTreeMap::new();
+        for file in files {
+            if !ProjectMeta::is_meta_file(file) {
+                continue;
+            }
+            let Some(project_dir) = file.parent() else {
+                continue;
+            };
+            if metas.contains_key(project_dir) {
+                continue;
+            }
+            if let Some(meta) = ProjectMeta::load(project_dir)? {
+                metas.insert(project_dir.to_path_buf(), meta);
+            }
+        }
+        Ok(metas)
+    }
+
+    /// ファイルに適用されるプロジェクト設定（最も近い親フォルダのもの）
+    fn meta_for<'a>(
+        metas: &'a BTreeMap<PathBuf, ProjectMeta>,
+        file: &Path,
+    ) -> Option<(&'a PathBuf, &'a ProjectMeta)> {
+        file.ancestors()
+            .skip(1)
+            .find_map(|dir| metas.get_key_value(dir))
+    }
+
+    /// プロジェクト設定の showcase で指定された成果物
+    fn showcase_items(
+        projects_dir: &Path,
+        metas: &BTreeMap<PathBuf, ProjectMeta>,
+        used_keys: &mut BTreeSet<String>,
+    ) -> Vec<GalleryItem> {
+        let mut items = Vec::new();
+
+        for (project_dir, meta) in metas {
+            if meta.exclude || meta.showcase.is_empty() {
+                continue;
+            }
+
+            let dir_name = project_dir
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let pattern = NamingRule::project_folder_name(&dir_name);
+
+            let mut index = 0;
+            for relative in &meta.showcase {
+                // 絶対パスや `..` でプロジェクトの外のファイルを載せない
+                let is_relative = relative
+                    .components()
+                    .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+                if !is_relative {
+                    UI::warning(&format!(
+                        "showcase にはプロジェクトフォルダ内の相対パスを指定してください: {} ({})",
+                        relative.display(),
+                        project_dir.display()
+                    ));
+                    continue;
+                }
+                let target = project_dir.join(relative);
+                if !target.is_file() {
+                    UI::warning(&format!(
+                        "showcase に指定されたファイルが見つかりません: {}",
+                        target.display()
+                    ));
+                    continue;
+                }
+                // シンボリックリンクで外を指している場合も除く
+                let inside = fs::canonicalize(&target)
+                    .ok()
+                    .zip(fs::canonicalize(project_dir).ok())
+                    .is_some_and(|(target, project_dir)| target.starts_with(project_dir));
+                if !inside {
+                    UI::warning(&format!(
+                        "showcase に指定されたファイルがプロジェクトフォルダの外にあります: {}",
+                        target.display()
+                    ));
+                    continue;
+                }
+
+                let extension = target
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .unwrap_or("")
+                    .to_lowercase();
+                let kind = MediaKind::from_extension(&extension).unwrap_or(MediaKind::Document);
+
+                // 命名規則に沿わないフォルダは成果物の更新日を日付として使う
+                let (date, title) = match &pattern {
+                    Some((date, project)) => (date.clone(), project.clone()),
+                    None => {
+                        let modified = fs::metadata(&target)
+                            .and_then(|meta| meta.modified())
+                            .map(chrono::DateTime::<chrono::Local>::from)
+                            .unwrap_or_else(|_| chrono::Local::now());
+                        (modified.format("%Y%m%d").to_string(), dir_name.clone())
+                    }
+                };
+                let base_key = match &pattern {
+                    Some((date, project)) => format!("{}_{}", date, project),
+                    None => dir_name.clone(),
+                };
+
+                // 2 件目以降や、探索の結果・別カテゴリの同名プロジェクトと重なる場合は
+                // `-2`, `-3` ... を付けて区別する
+                let first = index == 0;
+                let key = loop {
+                    index += 1;
+                    let candidate = if index == 1 {
+                        base_key.clone()
+                    } else {
+                        format!("{}-{}", base_key, index)
+                    };
+                    if used_keys.insert(candidate.clone()) {
+                        break candidate;
+                    }
+                };
+                if first && key != base_key {
+                    UI::warning(&format!(
+                        "エントリ名が重なるため {} を {} にします: {}",
+                        base_key,
+                        key,
+                        target.display()
+                    ));
+                }
+
+                items.push(GalleryItem {
+                    key,
+                    title,
+                    date,
+                    category: Self::category_of(projects_dir, &target),
+                    target,
+                    kind,
+                    score: 1.0,
+                    tags: Vec::new(),
+                    entries: Vec::new(),
+                });
+            }
+        }
+
+        items
+    }
+
     /// YYYYMMDD_projectname パターンのフォルダと成果物をマッチング
-    fn find_matching_outputs(files: &[PathBuf]) -> Result<HashMap<String, PathBuf>> {
-        let mut matches = HashMap::new();
-        
-        // メディアファイルの拡張子
-        let media_extensions = [
-            "mp4", "avi", "mov", "mkv", "wmv", "flv", "webm",
-            "mp3", "wav", "flac", "aac", "ogg",
-            "png", "jpg", "jpeg", "gif", "bmp", "webp",
-            "pdf", "psd", "ai", "svg",
-        ];
-        
+    ///
+    /// 1 つのプロジェクトに複数の候補がある場合は `select_output` の規則で 1 件に絞る
+    /// （`per_kind` の場合はメディア種別ごとに 1 件）。
+    fn find_matching_outputs(
+        projects_dir: &Path,
+        files: &[PathBuf],
+        options: &GalleryOptions,
+    ) -> Result<Vec<GalleryItem>> {
+        // 別のカテゴリにある同名のプロジェクトを混ぜないように、カテゴリごとに分ける
+        let mut candidates: BTreeMap<(Option<String>, String), Vec<OutputCandidate>> =
+            BTreeMap::new();
+
         for file in files {
             let file_name = match file.file_name() {
                 Some(name) => name.to_string_lossy().to_string(),
                 None => continue,
             };
-            
+
             // 拡張子チェック
             let extension = file
                 .extension()
                 .and_then(|e| e.to_str())
                 .unwrap_or("")
                 .to_lowercase();
-            
-            if !media_extensions.contains(&extension.as_str()) {
+
+            let Some(kind) = MediaKind::from_extension(&extension) else {
+                continue;
+            };
+
+            // YYYYMMDD_projectname パターンをチェック（_v2 / _final などの版表記は除く）
+            let Some((date_part, file_project)) = Self::extract_project_pattern(&file_name) else {
+                continue;
+            };
+            let (project_part, revision) = Revision::split(&file_project);
+
+            // 親ディレクトリ名もチェック
+            let Some(dir_str) = file
+                .parent()
+                .and_then(|parent| parent.file_name())
+                .map(|name| name.to_string_lossy().to_string())
+            else {
+                continue;
+            };
+
+            // 表記ゆれ（大文字小文字・全角半角・区切り文字）を吸収して一致度を測る
+            let dir_pattern = NamingRule::project_folder_name(&dir_str);
+            let dir_project = dir_pattern
+                .as_ref()
+                .map(|(_, project)| project.as_str())
+                .unwrap_or(dir_str.as_str());
+            let score = NamingRule::name_similarity(&project_part, dir_project);
+            if score < options.match_threshold {
                 continue;
             }
-            
-            // YYYYMMDD_projectname パターンをチェック
-            if let Some((date_part, project_part)) = Self::extract_project_pattern(&file_name) {
-                // 親ディレクトリ名もチェック
-                if let Some(parent_dir) = file.parent() {
-                    if let Some(dir_name) = parent_dir.file_name() {
-                        let dir_str = dir_name.to_string_lossy();
-                        
-                        // ディレクトリ名が YYYYMMDD_projectname 形式で、
-                        // ファイル名のプロジェクト部分と一致する場合
-                        if let Some((dir_date, dir_project)) = Self::extract_project_pattern(&dir_str) {
-                            if dir_project == project_part {
-                                let key = format!("{}_{}", dir_date, dir_project);
-                                matches.insert(key, file.clone());
-                                continue;
-                            }
-                        }
-                        
-                        // ディレクトリ名がプロジェクト名と部分一致する場合
-                        if dir_str.contains(&project_part) {
-                            let key = format!("{}_{}", date_part, project_part);
-                            matches.insert(key, file.clone());
-                        }
-                    }
+
+            let key = match &dir_pattern {
+                // ディレクトリ名が YYYYMMDD_projectname 形式ならフォルダ名を採用
+                Some((dir_date, dir_project)) => format!("{}_{}", dir_date, dir_project),
+                // そうでなければファイル名の日付とプロジェクト名を採用
+                None => format!("{}_{}", date_part, project_part),
+            };
+
+            let modified = fs::metadata(file).and_then(|meta| meta.modified()).ok();
+            let category = Self::category_of(projects_dir, file);
+            candidates.entry((category, key)).or_default().push(OutputCandidate {
+                path: file.clone(),
+                extension,
+                kind,
+                revision,
+                score,
+                modified,
+            });
+        }
+
+        let mut items = Vec::new();
+        let mut used_keys: BTreeSet<String> = BTreeSet::new();
+        for ((category, base_key), group) in candidates {
+            // キーは必ず `YYYYMMDD_projectname` の形
+            let (date, title) = base_key
+                .split_once('_')
+                .map(|(date, title)| (date.to_string(), title.to_string()))
+                .unwrap_or_default();
+
+            // 別のカテゴリの同名プロジェクトと重なる場合は `-2`, `-3` ... を付けて区別する
+            let key = (1..)
+                .map(|index| match index {
+                    1 => base_key.clone(),
+                    _ => format!("{}-{}", base_key, index),
+                })
+                .find(|candidate| used_keys.insert(candidate.clone()))
+                .unwrap_or_default();
+            if key != base_key {
+                UI::warning(&format!(
+                    "エントリ名が重なるため {} を {} にします: {}",
+                    base_key,
+                    key,
+                    projects_dir.join(category.as_deref().unwrap_or_default()).display()
+                ));
+            }
+
+            if options.per_kind {
+                let mut by_kind: BTreeMap<MediaKind, Vec<OutputCandidate>> = BTreeMap::new();
+                for candidate in group {
+                    by_kind.entry(candidate.kind).or_default().push(candidate);
                 }
+                for (_, group) in by_kind {
+                    let best = Self::select_output(group, &options.preferred_extensions);
+                    items.push(GalleryItem {
+                        key: format!("{}.{}", key, best.extension),
+                        title: title.clone(),
+                        date: date.clone(),
+                        category: category.clone(),
+                        target: best.path,
+                        kind: best.kind,
+                        score: best.score,
+                        tags: Vec::new(),
+                        entries: Vec::new(),
+                    });
+                }
+            } else {
+                let best = Self::select_output(group, &options.preferred_extensions);
+                items.push(GalleryItem {
+                    key,
+                    title,
+                    date,
+                    category,
+                    target: best.path,
+                    kind: best.kind,
+                    score: best.score,
+                    tags: Vec::new(),
+                    entries: Vec::new(),
+                });
             }
         }
-        
-        Ok(matches)
+
+        Ok(items)
+    }
+
+    /// 1_projects/[category]/[project]/... の category 部分を取り出す
+    fn category_of(projects_dir: &Path, file: &Path) -> Option<String> {
+        let relative = file.strip_prefix(projects_dir).ok()?;
+        let components: Vec<_> = relative.components().collect();
+        // category/project/file の 3 階層以上あるときだけカテゴリとみなす
+        if components.len() < 3 {
+            return None;
+        }
+        Some(components[0].as_os_str().to_string_lossy().to_string())
+    }
+
+    /// 並べ方とビューの設定から、成果物 1 件分のエントリ名を組み立てる
+    fn entry_names(item: &GalleryItem, options: &GalleryOptions) -> Vec<String> {
+        let mut entries = Vec::new();
+
+        match (options.layout, &item.category) {
+            (GalleryLayout::Category, Some(category)) => {
+                entries.push(format!("{}/{}", category, item.key));
+            }
+            _ => entries.push(item.key.clone()),
+        }
+
+        for view in &options.views {
+            let group = match view {
+                GalleryView::Year => item
+                    .date
+                    .get(..4)
+                    .filter(|year| year.chars().all(|c| c.is_ascii_digit()))
+                    .unwrap_or("unknown")
+                    .to_string(),
+                GalleryView::Kind => item.kind.folder_name().to_string(),
+            };
+            entries.push(format!("{}/{}/{}", view.folder_name(), group, item.key));
+        }
+
+        entries
     }
 
-    /// YYYYMMDD_projectname パターンから日付とプロジェクト名を抽出
+    /// 候補から 1 件を選ぶ
+    ///
+    /// 優先順位: フォルダ名との一致度 → 版表記（`_final` > `_v3` > `_v2` > 表記なし）→
+    /// 拡張子の優先順 → 更新日時が新しいもの → パスの辞書順。ファイルの走査順には依存しない。
+    fn select_output(
+        mut group: Vec<OutputCandidate>,
+        preferred_extensions: &[String],
+    ) -> OutputCandidate {
+        let extension_rank = |extension: &str| {
+            preferred_extensions
+                .iter()
+                .position(|preferred| preferred.eq_ignore_ascii_case(extension))
+                .unwrap_or(preferred_extensions.len())
+        };
+
+        group.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| b.revision.cmp(&a.revision))
+                .then_with(|| extension_rank(&a.extension).cmp(&extension_rank(&b.extension)))
+                .then_with(|| b.modified.cmp(&a.modified))
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        group.swap_remove(0)
+    }
+
+    /// YYYYMMDD_projectname パターンから日付とプロジェクト名を抽出（実在しない日付は除く）
     fn extract_project_pattern(name: &str) -> Option<(String, String)> {
         // 拡張子を除去
         let name_without_ext = if let Some(dot_pos) = name.rfind('.') {
@@ -154,47 +1051,131 @@ impl GalleryManager {
         } else {
             name
         };
-        
-        let parts: Vec<&str> = name_without_ext.split('_').collect();
-        if parts.len() < 2 {
-            return None;
+
+        NamingRule::split_dated_name(name_without_ext)
+    }
+
+    /// Looker（以前のバージョンを含む）が作成したエントリかどうか
+    ///
+    /// 以前のバージョンは .lnk という名前のシンボリックリンクや、
+    /// `Target: ...` と書いただけのテキストファイルを作成していた。
+    /// 利用者が自分で置いたファイルは上書きしない。
+    fn is_looker_entry(path: &Path) -> bool {
+        let Ok(metadata) = path.symlink_metadata() else {
+            return false;
+        };
+        if metadata.file_type().is_symlink() {
+            return true;
         }
-        
-        let date_part = parts[0];
-        // YYYYMMDD 形式かチェック (8桁の数字)
-        if date_part.len() == 8 && date_part.chars().all(|c| c.is_ascii_digit()) {
-            let project_part = parts[1..].join("_");
-            return Some((date_part.to_string(), project_part));
+        if ShellLink::read(path).is_ok() {
+            return true;
         }
-        
-        None
+        fs::read(path)
+            .map(|content| content.starts_with(b"Target: "))
+            .unwrap_or(false)
     }
+}
 
-    /// ショートカットを作成
-    #[cfg(target_os = "windows")]
-    fn create_shortcut(target: &Path, link_path: &Path) -> Result<bool> {
-        use std::os::windows::fs::symlink_file;
-        
-        // Windowsではシンボリックリンクまたは.lnkファイルを作成
-        // 権限の問題でシンボリックリンクが作れない場合もあるため、
-        // 簡易的にハードリンクまたはコピーで対応
-        match symlink_file(target, link_path) {
-            Ok(_) => Ok(true),
-            Err(_) => {
-                // シンボリックリンクが失敗した場合、情報ファイルを作成
-                let info = format!("Target: {}", target.display());
-                fs::write(link_path, info)?;
-                Ok(true)
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn removes_only_empty_folders_that_held_entries() {
+        let gallery = TempDir::new("gallery-cleanup");
+        for dir in ["animation/2024", "mine", "_by-year/2023", "kept"] {
+            fs::create_dir_all(gallery.join(dir)).unwrap();
         }
+        fs::write(gallery.join("kept/20240301_promo.lnk"), b"").unwrap();
+        let links = [
+            "animation/2024/20240301_promo.lnk".to_string(),
+            "_by-year/2023/20230101_old.lnk".to_string(),
+            "kept/20240301_promo.lnk".to_string(),
+        ];
+
+        GalleryManager::remove_empty_entry_dirs(&gallery, &links).unwrap();
+
+        assert!(!gallery.join("animation").exists());
+        assert!(!gallery.join("_by-year").exists());
+        assert!(gallery.join("kept").is_dir());
+        assert!(gallery.join("mine").is_dir());
+        assert!(gallery.is_dir());
     }
 
-    #[cfg(not(target_os = "windows"))]
-    fn create_shortcut(target: &Path, link_path: &Path) -> Result<bool> {
-        use std::os::unix::fs::symlink;
-        
-        symlink(target, link_path)
-            .with_context(|| format!("シンボリックリンクの作成に失敗: {:?} -> {:?}", link_path, target))?;
-        Ok(true)
+    #[test]
+    fn showcase_rejects_paths_outside_the_project() {
+        let root = TempDir::new("showcase-escape");
+        let project = root.join("1_projects/motion/20240301_promo");
+        fs::create_dir_all(project.join("render")).unwrap();
+        fs::write(project.join("render/final.mp4"), b"").unwrap();
+        fs::write(root.join("secret.pdf"), b"").unwrap();
+        let secret = root.join("secret.pdf");
+        fs::write(
+            project.join("project.toml"),
+            format!(
+                "showcase = [\"render/final.mp4\", \"../../../secret.pdf\", {:?}]\n",
+                secret.to_string_lossy()
+            ),
+        )
+        .unwrap();
+
+        let options = GalleryOptions::from_config(&LookerConfig::default());
+        let plan = GalleryManager::plan(&root, &options).unwrap();
+        let targets: Vec<&PathBuf> = plan.items.iter().map(|item| &item.target).collect();
+        assert_eq!(targets, vec![&project.join("render/final.mp4")]);
+    }
+
+    #[test]
+    fn same_project_name_in_two_categories_keeps_both() {
+        let root = TempDir::new("gallery-categories");
+        for category in ["film", "club"] {
+            let project = root.join(format!("1_projects/{category}/20240301_promo"));
+            fs::create_dir_all(&project).unwrap();
+            fs::write(project.join("20240301_promo.mp4"), b"").unwrap();
+        }
+
+        let options = GalleryOptions::from_config(&LookerConfig::default());
+        let plan = GalleryManager::plan(&root, &options).unwrap();
+        let items: Vec<(&str, Option<&str>, &str)> = plan
+            .items
+            .iter()
+            .map(|item| (item.key.as_str(), item.category.as_deref(), item.title.as_str()))
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                ("20240301_promo", Some("club"), "promo"),
+                ("20240301_promo-2", Some("film"), "promo"),
+            ]
+        );
+        assert!(plan.items[1].target.starts_with(root.join("1_projects/film")));
+    }
+
+    #[test]
+    fn duplicate_keys_get_a_suffix() {
+        let root = TempDir::new("gallery-dedupe");
+        let project = root.join("1_projects/motion/20240301_promo");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join("20240301_promo.mp4"), b"").unwrap();
+        // 同じ名前のプロジェクトを showcase で 2 件載せる
+        let showcase = root.join("1_projects/web/20240301_promo");
+        fs::create_dir_all(&showcase).unwrap();
+        fs::write(showcase.join("site.png"), b"").unwrap();
+        fs::write(showcase.join("banner.png"), b"").unwrap();
+        fs::write(
+            showcase.join("project.toml"),
+            "showcase = [\"site.png\", \"banner.png\"]\n",
+        )
+        .unwrap();
+
+        let options = GalleryOptions::from_config(&LookerConfig::default());
+        let plan = GalleryManager::plan(&root, &options).unwrap();
+        let keys: BTreeSet<&str> = plan.items.iter().map(|item| item.key.as_str()).collect();
+        assert_eq!(plan.items.len(), 3);
+        assert_eq!(
+            keys,
+            BTreeSet::from(["20240301_promo", "20240301_promo-2", "20240301_promo-3"])
+        );
     }
 }
@@ -0,0 +1,424 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use chrono::Local;
+use serde::Serialize;
+
+use crate::config::LookerConfig;
+use crate::download_manager::{DownloadKind, DownloadManager};
+use crate::gallery_manager::{GalleryManager, GalleryOptions};
+use crate::naming::NamingRule;
+use crate::project_meta::ProjectMeta;
+use crate::record_manager::{ActionType, RecordManager, RecordOptions};
+use crate::routing_rules::RoutingRules;
+use crate::scan_index::ScanIndex;
+use crate::scanner::DriveScanner;
+use crate::structure_manager::{StructureDefinition, StructureManager};
+use crate::ui::UI;
+
+/// ドライブ全体の状態をまとめる
+pub struct WorkspaceHealth;
+
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub root: PathBuf,
+    /// 作成日時（`YYYY-MM-DD HH:MM`）
+    pub generated_at: String,
+    pub structure: StructureHealth,
+    /// フォルダが無いなどで集計できなかった項目は None
+    pub downloads: Option<DownloadsHealth>,
+    pub records: Option<RecordsHealth>,
+    pub gallery: Option<GalleryHealth>,
+    pub sizes: Vec<FolderSize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StructureHealth {
+    pub profile: String,
+    pub completion_rate: f64,
+    pub missing: Vec<String>,
+    pub category_issues: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DownloadsHealth {
+    /// 種類ごとの未整理のファイル数
+    pub kinds: Vec<KindCount>,
+    pub folders: usize,
+    pub oldest: Option<OldestDownload>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct KindCount {
+    pub kind: DownloadKind,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OldestDownload {
+    pub path: PathBuf,
+    pub days: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecordsHealth {
+    /// record 直下など種別フォルダに入っていないファイル
+    pub unsorted: usize,
+    /// 命名規則に合わないファイル
+    pub misnamed: usize,
+    /// 種別の違うフォルダにあるファイル
+    pub misplaced: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GalleryHealth {
+    pub projects: usize,
+    pub projects_without_outputs: Vec<PathBuf>,
+    pub dangling_links: Vec<DanglingLink>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DanglingLink {
+    pub link: PathBuf,
+    pub target: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FolderSize {
+    pub folder: String,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+impl WorkspaceHealth {
+    /// 各機能のプランを読み取り専用で作って集計する（ファイルは変更しない）
+    pub fn collect(
+        root: &Path,
+        config: &LookerConfig,
+        definition: &StructureDefinition,
+        index: &ScanIndex,
+    ) -> Result<HealthReport> {
+        let validation = StructureManager::validate_structure(root, definition)?;
+        let structure = StructureHealth {
+            profile: validation.profile.clone(),
+            completion_rate: validation.completion_rate(),
+            missing: validation.missing.clone(),
+            category_issues: validation.category_issues.len(),
+        };
+
+        let downloads = Self::downloads(root, index)?;
+        let records = Self::records(root, RoutingRules::load(root)?, index)?;
+        let gallery = Self::gallery(root, config)?;
+        let sizes = Self::sizes(root, definition, index)?;
+
+        Ok(HealthReport {
+            root: root.to_path_buf(),
+            generated_at: Local::now().format("%Y-%m-%d %H:%M").to_string(),
+            structure,
+            downloads,
+            records,
+            gallery,
+            sizes,
+        })
+    }
+
+    fn downloads(root: &Path, index: &ScanIndex) -> Result<Option<DownloadsHealth>> {
+        let downloads_dir = root.join("0_inbox").join("downloads");
+        if !downloads_dir.is_dir() {
+            return Ok(None);
+        }
+
+        let mut kinds: BTreeMap<DownloadKind, usize> = BTreeMap::new();
+        let mut oldest: Option<(PathBuf, chrono::DateTime<Local>)> = None;
+        for file in RecordManager::scan_record_folder(&downloads_dir, index)? {
+            if file.name.starts_with('.') {
+                continue;
+            }
+            let kind = DownloadManager::classify(&file.name, &file.extension);
+            if kind == DownloadKind::Partial {
+                continue;
+            }
+            *kinds.entry(kind).or_default() += 1;
+            if oldest.as_ref().is_none_or(|(_, modified)| file.modified < *modified) {
+                oldest = Some((file.path, file.modified));
+            }
+        }
+
+        Ok(Some(DownloadsHealth {
+            kinds: kinds
+                .into_iter()
+                .map(|(kind, count)| KindCount { kind, count })
+                .collect(),
+            folders: Self::subdirs(&downloads_dir).len(),
+            oldest: oldest.map(|(path, modified)| OldestDownload {
+                path,
+                days: (Local::now() - modified).num_days(),
+            }),
+        }))
+    }
+
+    fn records(
+        root: &Path,
+        rules: RoutingRules,
+        index: &ScanIndex,
+    ) -> Result<Option<RecordsHealth>> {
+        let record_root = root.join("0_inbox").join("record");
+        if !record_root.is_dir() {
+            return Ok(None);
+        }
+        let options = RecordOptions {
+            rules,
+            ..RecordOptions::new(index.clone())
+        };
+        let plan = RecordManager::plan(&record_root, &options)?;
+
+        // 同じファイルに複数のアクションがあっても 1 件と数える
+        let mut health = RecordsHealth {
+            unsorted: 0,
+            misnamed: 0,
+            misplaced: 0,
+        };
+        let mut seen = BTreeSet::new();
+        for action in &plan.actions {
+            if !seen.insert(&action.source) {
+                continue;
+            }
+            if action.source.parent() == Some(record_root.as_path()) {
+                health.unsorted += 1;
+            } else if action.action_type == ActionType::MoveToCorrectLocation {
+                health.misplaced += 1;
+            } else if action.action_type == ActionType::Rename {
+                health.misnamed += 1;
+            }
+        }
+        Ok(Some(health))
+    }
+
+    fn gallery(root: &Path, config: &LookerConfig) -> Result<Option<GalleryHealth>> {
+        let projects_dir = root.join("1_projects");
+        if !projects_dir.is_dir() {
+            return Ok(None);
+        }
+        let plan = GalleryManager::plan(root, &GalleryOptions::from_config(config))?;
+
+        let mut projects = Vec::new();
+        for category in Self::subdirs(&projects_dir) {
+            let name = category
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            // ギャラリーの探索と同じ判定で数える（名前に空白があるプロジェクトも含む）
+            if NamingRule::project_folder_name(&name).is_some() {
+                // カテゴリに入っていないプロジェクト
+                projects.push(category);
+                continue;
+            }
+            projects.extend(Self::subdirs(&category).into_iter().filter(|project| {
+                project.file_name().is_some_and(|name| {
+                    NamingRule::project_folder_name(&name.to_string_lossy()).is_some()
+                })
+            }));
+        }
+
+        let mut without_outputs = Vec::new();
+        for project in &projects {
+            let excluded = ProjectMeta::load(project)?.is_some_and(|meta| meta.exclude);
+            if !excluded && !plan.items.iter().any(|item| item.target.starts_with(project)) {
+                without_outputs.push(project.clone());
+            }
+        }
+
+        Ok(Some(GalleryHealth {
+            projects: projects.len(),
+            projects_without_outputs: without_outputs,
+            dangling_links: GalleryManager::find_dangling_entries(root)?
+                .into_iter()
+                .map(|entry| DanglingLink {
+                    link: entry.link,
+                    target: entry.target,
+                })
+                .collect(),
+        }))
+    }
+
+    /// 標準フォルダ構造の最上位のフォルダごとのファイル数と合計サイズ
+    fn sizes(
+        root: &Path,
+        definition: &StructureDefinition,
+        index: &ScanIndex,
+    ) -> Result<Vec<FolderSize>> {
+        let mut sizes = Vec::new();
+        for entry in &definition.entries {
+            if entry.path.contains('/') {
+                continue;
+            }
+            let dir = root.join(&entry.path);
+            if !dir.is_dir() {
+                continue;
+            }
+            let files: Vec<_> = DriveScanner::scan(&dir, index)?
+                .into_iter()
+                .filter(|file| !file.is_dir)
+                .collect();
+            sizes.push(FolderSize {
+                folder: entry.path.clone(),
+                files: files.len(),
+                bytes: files.iter().map(|file| file.size).sum(),
+            });
+        }
+        Ok(sizes)
+    }
+
+    fn subdirs(dir: &Path) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .filter(|path| {
+                        path.is_dir()
+                            && !path
+                                .file_name()
+                                .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        dirs.sort();
+        dirs
+    }
+}
+
+impl HealthReport {
+    /// Markdown の表にする（Wiki や Issue への貼り付け用）
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# ワークスペースの状態\n");
+        let _ = writeln!(
+            out,
+            "- ルート: `{}`\n- 作成日時: {}\n",
+            self.root.display(),
+            self.generated_at
+        );
+
+        let _ = writeln!(out, "## 標準フォルダ構造\n");
+        let _ = writeln!(out, "| 項目 | 値 |\n| --- | --- |");
+        let _ = writeln!(out, "| プロファイル | {} |", self.structure.profile);
+        let _ = writeln!(out, "| 完成度 | {:.1}% |", self.structure.completion_rate);
+        let _ = writeln!(out, "| 不足しているフォルダ | {} |", self.structure.missing.len());
+        let _ = writeln!(out, "| カテゴリの問題 | {} |", self.structure.category_issues);
+        if !self.structure.missing.is_empty() {
+            out.push('\n');
+            for folder in &self.structure.missing {
+                let _ = writeln!(out, "- `{}`", folder);
+            }
+        }
+        out.push('\n');
+
+        let _ = writeln!(out, "## 未整理のファイル\n");
+        match &self.downloads {
+            Some(downloads) => {
+                let _ = writeln!(out, "| downloads の種類 | 件数 |\n| --- | ---: |");
+                for kind in &downloads.kinds {
+                    let _ = writeln!(out, "| {} | {} |", kind.kind.label(), kind.count);
+                }
+                let _ = writeln!(out, "| フォルダ | {} |", downloads.folders);
+                if let Some(oldest) = &downloads.oldest {
+                    let _ = writeln!(
+                        out,
+                        "\n最も古いダウンロード: `{}`（{} 日前）",
+                        oldest.path.display(),
+                        oldest.days
+                    );
+                }
+            }
+            None => {
+                let _ = writeln!(out, "downloads フォルダがありません。");
+            }
+        }
+        out.push('\n');
+        match &self.records {
+            Some(records) => {
+                let _ = writeln!(out, "| record | 件数 |\n| --- | ---: |");
+                let _ = writeln!(out, "| 種別フォルダ外 | {} |", records.unsorted);
+                let _ = writeln!(out, "| 命名規則違反 | {} |", records.misnamed);
+                let _ = writeln!(out, "| 種別の取り違え | {} |", records.misplaced);
+            }
+            None => {
+                let _ = writeln!(out, "record フォルダがありません。");
+            }
+        }
+        out.push('\n');
+
+        let _ = writeln!(out, "## プロジェクトとギャラリー\n");
+        match &self.gallery {
+            Some(gallery) => {
+                let _ = writeln!(out, "| 項目 | 件数 |\n| --- | ---: |");
+                let _ = writeln!(out, "| プロジェクト | {} |", gallery.projects);
+                let _ = writeln!(
+                    out,
+                    "| 成果物の無いプロジェクト | {} |",
+                    gallery.projects_without_outputs.len()
+                );
+                let _ = writeln!(out, "| リンク切れ | {} |", gallery.dangling_links.len());
+                if !gallery.projects_without_outputs.is_empty() {
+                    let _ = writeln!(out, "\n成果物の無いプロジェクト:\n");
+                    for project in &gallery.projects_without_outputs {
+                        let _ = writeln!(out, "- `{}`", project.display());
+                    }
+                }
+                if !gallery.dangling_links.is_empty() {
+                    let _ = writeln!(out, "\nリンク切れ:\n");
+                    for link in &gallery.dangling_links {
+                        let _ = writeln!(
+                            out,
+                            "- `{}` → `{}`",
+                            link.link.display(),
+                            link.target.display()
+                        );
+                    }
+                }
+            }
+            None => {
+                let _ = writeln!(out, "1_projects フォルダがありません。");
+            }
+        }
+        out.push('\n');
+
+        let _ = writeln!(out, "## フォルダごとのサイズ\n");
+        let _ = writeln!(out, "| フォルダ | ファイル数 | サイズ |\n| --- | ---: | ---: |");
+        for size in &self.sizes {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} |",
+                size.folder,
+                size.files,
+                UI::format_size(size.bytes)
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn gallery_counts_projects_with_spaces_in_the_name() {
+        let root = TempDir::new("health");
+        let project = root.join("1_projects/film/20240301_My Project");
+        fs::create_dir_all(&project).unwrap();
+        fs::create_dir_all(root.join("1_projects/film/notes")).unwrap();
+
+        let health = WorkspaceHealth::gallery(&root, &LookerConfig::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(health.projects, 1);
+        assert_eq!(health.projects_without_outputs, vec![project]);
+    }
+}
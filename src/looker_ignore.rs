@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::ui::UI;
+
+/// `.lookerignore` と OS が作るファイルによる除外
+///
+/// `.lookerignore` は gitignore と同じ書き方で、置いたフォルダ以下に効く。
+/// 深い階層のファイルほど優先され、`!` で上の階層の除外を取り消せる。
+/// ワークスペースのルートより上のフォルダの `.lookerignore` は読まない。
+/// 複製しても読み込んだ `.lookerignore` のキャッシュを共有する。
+#[derive(Debug, Clone)]
+pub struct LookerIgnore {
+    root: PathBuf,
+    /// フォルダごとの `.lookerignore`（無ければ None）
+    matchers: Arc<Mutex<HashMap<PathBuf, Option<Arc<Gitignore>>>>>,
+}
+
+pub const IGNORE_FILE: &str = ".lookerignore";
+
+/// OS が作る管理用のファイル・フォルダ（大文字小文字を区別しない）
+const SYSTEM_PATTERNS: [&str; 10] = [
+    "desktop.ini",
+    "Thumbs.db",
+    "ehthumbs.db",
+    ".DS_Store",
+    "._*",
+    ".Spotlight-V100/",
+    ".Trashes/",
+    "$RECYCLE.BIN/",
+    "System Volume Information/",
+    IGNORE_FILE,
+];
+
+impl LookerIgnore {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            matchers: Arc::default(),
+        }
+    }
+
+    /// 除外するファイル・フォルダか
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Some(name) = path.file_name() else {
+            return false;
+        };
+        if Self::is_system_entry(&name.to_string_lossy(), is_dir) {
+            return true;
+        }
+
+        // 近いフォルダの `.lookerignore` から順に、最初に一致したもので決める
+        let dirs: Vec<&Path> = path
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with(&self.root))
+            .collect();
+        for matcher in self.matchers(&dirs).into_iter().flatten() {
+            let matched = matcher.matched_path_or_any_parents(path, is_dir);
+            if matched.is_ignore() {
+                return true;
+            }
+            if matched.is_whitelist() {
+                return false;
+            }
+        }
+        false
+    }
+
+    /// OS が作る管理用のファイル（`desktop.ini`・`Thumbs.db`・`.DS_Store` など）か
+    pub fn is_system_file(name: &str) -> bool {
+        Self::is_system_entry(name, false)
+    }
+
+    fn is_system_entry(name: &str, is_dir: bool) -> bool {
+        static SYSTEM: OnceLock<Gitignore> = OnceLock::new();
+        let system = SYSTEM.get_or_init(|| {
+            let mut builder = GitignoreBuilder::new("");
+            let _ = builder.case_insensitive(true);
+            for pattern in SYSTEM_PATTERNS {
+                let _ = builder.add_line(None, pattern);
+            }
+            builder.build().unwrap_or_else(|_| Gitignore::empty())
+        });
+        system.matched(name, is_dir).is_ignore()
+    }
+
+    /// 各フォルダの `.lookerignore`（`dirs` と同じ順）
+    ///
+    /// ロックはキャッシュを引くときと、新しく読み込んだものを入れるときの 2 回だけ取る。
+    fn matchers(&self, dirs: &[&Path]) -> Vec<Option<Arc<Gitignore>>> {
+        let cached: Vec<Option<Option<Arc<Gitignore>>>> = {
+            let matchers = self.matchers.lock().unwrap();
+            dirs.iter().map(|dir| matchers.get(*dir).cloned()).collect()
+        };
+        if cached.iter().all(Option::is_some) {
+            return cached.into_iter().flatten().collect();
+        }
+
+        // ファイルの読み込みはロックの外で行う
+        let mut loaded = Vec::new();
+        let result = dirs
+            .iter()
+            .zip(cached)
+            .map(|(dir, cached)| {
+                cached.unwrap_or_else(|| {
+                    let matcher = Self::load(dir);
+                    loaded.push((dir.to_path_buf(), matcher.clone()));
+                    matcher
+                })
+            })
+            .collect();
+        let mut matchers = self.matchers.lock().unwrap();
+        for (dir, matcher) in loaded {
+            matchers.entry(dir).or_insert(matcher);
+        }
+        result
+    }
+
+    fn load(dir: &Path) -> Option<Arc<Gitignore>> {
+        let path = dir.join(IGNORE_FILE);
+        path.is_file().then(|| {
+            let (matcher, error) = Gitignore::new(&path);
+            if let Some(error) = error {
+                // 読めなかった行だけを飛ばし、残りのパターンは使う
+                UI::warning(&format!("{} の読み込みに失敗: {error}", path.display()));
+            }
+            Arc::new(matcher)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use std::fs;
+
+    #[test]
+    fn builtin_patterns_ignore_os_files() {
+        for name in ["desktop.ini", "Thumbs.db", "THUMBS.DB", ".DS_Store", "._clip.mov", ".lookerignore"] {
+            assert!(LookerIgnore::is_system_file(name), "{name}");
+        }
+        assert!(!LookerIgnore::is_system_file("clip.mov"));
+
+        let ignore = LookerIgnore::new(Path::new("/drive"));
+        assert!(ignore.is_ignored(Path::new("/drive/$RECYCLE.BIN"), true));
+        assert!(ignore.is_ignored(Path::new("/drive/System Volume Information"), true));
+        // フォルダ用のパターンは同じ名前のファイルには効かない
+        assert!(!ignore.is_ignored(Path::new("/drive/.Trashes"), false));
+    }
+
+    #[test]
+    fn readme_example_keeps_screen_capture_folder() {
+        let root = TempDir::new("ignore-readme");
+        let capture = root.join("0_inbox/record/screen capture");
+        fs::create_dir_all(capture.join("_keep")).unwrap();
+        fs::write(capture.join(IGNORE_FILE), "_keep/\n").unwrap();
+        fs::write(root.join(IGNORE_FILE), "*.tmp\n2_assets/footage/raw_*/\n").unwrap();
+
+        let ignore = LookerIgnore::new(&root);
+        assert!(ignore.is_ignored(&capture.join("_keep"), true));
+        assert!(ignore.is_ignored(&capture.join("_keep/shot.png"), false));
+        assert!(!ignore.is_ignored(&capture.join("shot.png"), false));
+        // `_keep/` はフォルダだけに一致する
+        assert!(!ignore.is_ignored(&capture.join("_keep"), false));
+
+        assert!(ignore.is_ignored(&capture.join("render.tmp"), false));
+        assert!(ignore.is_ignored(&root.join("2_assets/footage/raw_0301"), true));
+        assert!(ignore.is_ignored(&root.join("2_assets/footage/raw_0301/a.mov"), false));
+        assert!(!ignore.is_ignored(&root.join("2_assets/footage/edit/raw_0301"), true));
+    }
+
+    #[test]
+    fn deeper_negation_overrides_the_root() {
+        let root = TempDir::new("ignore-negation");
+        let keep = root.join("2_assets/keep");
+        fs::create_dir_all(&keep).unwrap();
+        fs::write(root.join(IGNORE_FILE), "*.tmp\n").unwrap();
+        fs::write(keep.join(IGNORE_FILE), "!important.tmp\n").unwrap();
+
+        let ignore = LookerIgnore::new(&root);
+        assert!(ignore.is_ignored(&keep.join("other.tmp"), false));
+        assert!(!ignore.is_ignored(&keep.join("important.tmp"), false));
+        assert!(ignore.is_ignored(&root.join("important.tmp"), false));
+    }
+
+    #[test]
+    fn files_above_the_root_are_not_read() {
+        let outer = TempDir::new("ignore-outer");
+        let root = outer.join("workspace");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(outer.join(IGNORE_FILE), "*.png\n").unwrap();
+
+        let ignore = LookerIgnore::new(&root);
+        assert!(!ignore.is_ignored(&root.join("shot.png"), false));
+        assert!(LookerIgnore::new(&outer).is_ignored(&root.join("shot.png"), false));
+    }
+}
@@ -1,19 +1,52 @@
+mod asset_audit;
+mod config;
+mod disk_usage;
+mod download_manager;
+mod folder_note;
+mod gallery_index;
+mod gallery_link;
 mod gallery_manager;
+mod health_report;
+mod looker_ignore;
 mod menu;
 mod naming;
+mod project_archive;
+mod project_audit;
+mod project_meta;
+mod project_scaffold;
 mod record_manager;
+mod routing_rules;
+mod scan_index;
 mod scanner;
+mod shell_link;
+mod structure_drift;
 mod structure_manager;
+#[cfg(test)]
+mod test_support;
 mod ui;
 
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Result};
-use clap::{Parser, ValueEnum};
-use gallery_manager::GalleryManager;
+use asset_audit::AssetAudit;
+use clap::{Parser, Subcommand, ValueEnum};
+use config::LookerConfig;
+use disk_usage::{DiskUsage, UsageFilter};
+use download_manager::DownloadManager;
+use folder_note::{FolderNoteFormat, FolderNotes};
+use gallery_index::GalleryIndex;
+use gallery_link::LinkMode;
+use gallery_manager::{GalleryLayout, GalleryManager, GalleryOptions, GalleryView};
+use health_report::WorkspaceHealth;
 use menu::{Menu, MenuAction};
+use project_archive::ProjectArchive;
+use project_audit::ProjectAudit;
+use project_scaffold::ProjectScaffold;
 use record_manager::{RecordManager, RecordOptions, RecordType};
+use routing_rules::{parse_size, RoutingRules};
+use scan_index::ScanIndex;
+use structure_drift::StructureDrift;
 use structure_manager::StructureManager;
 use ui::UI;
 use walkdir::WalkDir;
@@ -38,6 +71,14 @@ struct Cli {
     #[arg(long)]
     fast: bool,
 
+    /// 走査キャッシュ（.looker/scan-index.json）を使わずにすべて読み直す
+    #[arg(long, global = true)]
+    rescan: bool,
+
+    /// フォルダの走査に使うスレッド数（既定は CPU のコア数、1 で並列化しない）
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: Option<u16>,
+
     /// プランされた変更を自動的に適用する
     #[arg(long)]
     apply: bool,
@@ -50,13 +91,158 @@ struct Cli {
     #[arg(long)]
     verbose: bool,
 
+    /// 0_inbox/downloads のファイルを種類ごとに振り分ける（--apply で適用）
+    #[arg(long)]
+    organize_downloads: bool,
+
+    /// この日数以上前のダウンロードを「古いファイル」として知らせる（省略時は設定ファイルの値）
+    #[arg(long, value_name = "DAYS")]
+    stale_days: Option<u64>,
+
     /// プロジェクト成果物のショートカットを作成
     #[arg(long)]
     create_shortcuts: bool,
 
+    /// ギャラリーのリンク形式（省略時は .looker/config.toml の設定）
+    #[arg(long, value_enum, value_name = "MODE")]
+    link_mode: Option<LinkKind>,
+
+    /// メディア種別（動画/画像/音声/文書）ごとにギャラリーエントリを作成
+    #[arg(long)]
+    gallery_per_kind: bool,
+
+    /// プロジェクトフォルダ名と成果物名の一致度のしきい値（0.0〜1.0）
+    #[arg(long, value_name = "SCORE", value_parser = config::parse_threshold)]
+    match_threshold: Option<f64>,
+
+    /// ギャラリーの並べ方（flat: 直下に並べる / category: 1_projects のカテゴリを再現）
+    #[arg(long, value_enum, value_name = "LAYOUT")]
+    gallery_layout: Option<LayoutKind>,
+
+    /// ギャラリーに追加するビュー（year / kind）。複数指定可
+    #[arg(long = "gallery-view", value_enum, value_name = "VIEW")]
+    gallery_views: Vec<ViewKind>,
+
+    /// 5_gallery/index.html（サムネイル付きの一覧ページ）を生成
+    #[arg(long)]
+    gallery_index: bool,
+
+    /// 5_gallery の絶対パスのシンボリックリンクを相対パスに変換（--apply で適用）
+    #[arg(long)]
+    migrate_relative_links: bool,
+
+    /// 1_projects の構成と命名をチェックし、名前の変更案を表示（--apply で適用）
+    #[arg(long)]
+    audit_projects: bool,
+
+    /// 2_assets の種類フォルダとカテゴリをチェックし、移動案を表示（--apply で適用）
+    #[arg(long)]
+    audit_assets: bool,
+
     /// 標準フォルダ構造を確認・作成
     #[arg(long)]
     ensure_structure: bool,
+
+    /// 標準フォルダ構造を確認するだけ（作成はしない）。不足があると終了コード 2 で終わる
+    #[arg(long)]
+    check_structure: bool,
+
+    /// 標準フォルダに説明ファイルを書く（`markdown` は _README.md、`desktop-ini` は Windows のヒント）
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    folder_notes: Option<NoteKind>,
+
+    /// 構造・未整理のファイル・ギャラリー・サイズをまとめたドライブの状態を表示
+    #[arg(long)]
+    health_report: bool,
+
+    /// 確認結果の出力形式
+    #[arg(long, global = true, value_enum, value_name = "FORMAT", default_value = "text")]
+    format: OutputFormat,
+
+    /// 標準フォルダ構造に無いフォルダを一覧し、近い標準フォルダへのまとめ方を表示（--apply で適用）
+    #[arg(long)]
+    structure_drift: bool,
+
+    /// 標準フォルダ構造のプロファイル（.looker/structures/<NAME>.tree、省略時は設定ファイルの値）
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// プロジェクトの操作
+    Project {
+        #[command(subcommand)]
+        action: ProjectCommand,
+    },
+    /// 振り分けルール（.looker/rules.toml）の操作
+    Rules {
+        #[command(subcommand)]
+        action: RulesCommand,
+    },
+    /// 標準フォルダ・record の月・プロジェクトごとのディスク使用量と大きいファイルを表示
+    Usage {
+        /// 集計するフォルダ（ルートからの相対パス）。省略時は標準フォルダ構造の最上位すべて
+        #[arg(long, value_name = "FOLDER")]
+        path: Option<String>,
+        /// 対象の拡張子（複数指定可）
+        #[arg(long = "ext", value_name = "EXT")]
+        extensions: Vec<String>,
+        /// このサイズ以上のファイルだけ（例: 100MB）
+        #[arg(long, value_name = "SIZE")]
+        min_size: Option<String>,
+        /// 最終更新からこの日数以上経ったファイルだけ
+        #[arg(long, value_name = "DAYS")]
+        older_than: Option<u64>,
+        /// 大きいファイルの表示件数
+        #[arg(long, value_name = "N", default_value_t = 20)]
+        top: usize,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum RulesCommand {
+    /// ファイルにどのルールが一致するかを表示
+    Test {
+        /// 確認するファイル
+        file: PathBuf,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ProjectCommand {
+    /// 今日の日付で 1_projects/<CATEGORY>/YYYYMMDD_<NAME> を作成
+    New {
+        /// 1_projects 直下のカテゴリ名
+        category: String,
+        /// プロジェクト名（日付を除いた部分）
+        name: String,
+    },
+    /// 完了したプロジェクトを 9_archive/projects/<CATEGORY>/ に zip で保存（--apply で適用）
+    Archive {
+        /// 対象のプロジェクト（category/YYYYMMDD_name またはフォルダ名）。省略時はすべて
+        projects: Vec<String>,
+        /// 最終更新からこの日数以上経ったプロジェクトだけを対象にする
+        #[arg(long, value_name = "DAYS")]
+        older_than: Option<u64>,
+        /// プランされた変更を適用する
+        #[arg(long)]
+        apply: bool,
+        /// 確認無しで適用する
+        #[arg(long, alias = "y")]
+        yes: bool,
+    },
+    /// 9_archive のアーカイブを元の場所に復元（省略時は一覧を表示）
+    Restore {
+        /// 復元するアーカイブ（zip のパス、category/YYYYMMDD_name またはフォルダ名）
+        archive: Option<String>,
+        /// 元の場所に既にフォルダがあっても上書きして展開する
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
@@ -76,6 +262,83 @@ impl From<RecordKind> for RecordType {
     }
 }
 
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum LinkKind {
+    Symlink,
+    RelativeSymlink,
+    Hardlink,
+    Shortcut,
+    Desktop,
+    Url,
+    Copy,
+}
+
+impl From<LinkKind> for LinkMode {
+    fn from(kind: LinkKind) -> Self {
+        match kind {
+            LinkKind::Symlink => LinkMode::Symlink,
+            LinkKind::RelativeSymlink => LinkMode::RelativeSymlink,
+            LinkKind::Hardlink => LinkMode::Hardlink,
+            LinkKind::Shortcut => LinkMode::Shortcut,
+            LinkKind::Desktop => LinkMode::Desktop,
+            LinkKind::Url => LinkMode::Url,
+            LinkKind::Copy => LinkMode::Copy,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Markdown,
+    Json,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum NoteKind {
+    Markdown,
+    DesktopIni,
+}
+
+impl From<NoteKind> for FolderNoteFormat {
+    fn from(kind: NoteKind) -> Self {
+        match kind {
+            NoteKind::Markdown => FolderNoteFormat::Markdown,
+            NoteKind::DesktopIni => FolderNoteFormat::DesktopIni,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum LayoutKind {
+    Flat,
+    Category,
+}
+
+impl From<LayoutKind> for GalleryLayout {
+    fn from(kind: LayoutKind) -> Self {
+        match kind {
+            LayoutKind::Flat => GalleryLayout::Flat,
+            LayoutKind::Category => GalleryLayout::Category,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum ViewKind {
+    Year,
+    Kind,
+}
+
+impl From<ViewKind> for GalleryView {
+    fn from(kind: ViewKind) -> Self {
+        match kind {
+            ViewKind::Year => GalleryView::Year,
+            ViewKind::Kind => GalleryView::Kind,
+        }
+    }
+}
+
 fn main() -> Result<()> {
     if std::env::args().len() == 1 {
         return run_interactive_mode();
@@ -86,28 +349,350 @@ fn main() -> Result<()> {
 fn run_cli_mode() -> Result<()> {
     let args = Cli::parse();
 
+    if let Some(jobs) = args.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs.into())
+            .build_global()
+            .map_err(|err| anyhow!("スレッドプールの作成に失敗: {err}"))?;
+    }
+
+    if let Some(command) = &args.command {
+        return match command {
+            Command::Project {
+                action: ProjectCommand::New { category, name },
+            } => {
+                let root = get_drive_root()?;
+                let config = LookerConfig::load(&root)?;
+                ProjectScaffold::create(&root, category, name, &config.project).map(|_| ())
+            }
+            Command::Project {
+                action:
+                    ProjectCommand::Archive {
+                        projects,
+                        older_than,
+                        apply,
+                        yes,
+                    },
+            } => {
+                let root = get_drive_root()?;
+                let plan = ProjectArchive::plan(&root, projects, *older_than)?;
+                UI::render_archive_plan(&plan, args.verbose);
+
+                if plan.ready().next().is_none() {
+                    println!("アーカイブするプロジェクトはありません。");
+                    return Ok(());
+                }
+                if !(*apply || *yes) {
+                    println!("\n--apply を付けると、上記の変更を適用します。");
+                    return Ok(());
+                }
+                if !*yes && !confirm("アーカイブして元のフォルダを削除しますか？")? {
+                    println!("適用をキャンセルしました。");
+                    return Ok(());
+                }
+                ProjectArchive::apply(&root, &plan)
+            }
+            Command::Project {
+                action: ProjectCommand::Restore { archive, force },
+            } => {
+                let root = get_drive_root()?;
+                let Some(archive) = archive else {
+                    let archives = ProjectArchive::list(&root)?;
+                    UI::render_archive_list(&archives, true);
+                    return Ok(());
+                };
+                let zip_path = ProjectArchive::find_archive(&root, archive)?;
+                ProjectArchive::restore(&root, &zip_path, *force)?;
+                println!("ギャラリーに戻すには --create-shortcuts を実行してください。");
+                Ok(())
+            }
+            Command::Rules {
+                action: RulesCommand::Test { file },
+            } => {
+                // 整理のときと同じく record フォルダの位置からルートを決める
+                let record_root = resolve_record_root(args.record_path.as_deref())?;
+                let root = workspace_root(&record_root)?;
+                let rules = RoutingRules::load(&root)?;
+                let file = RoutingRules::file_info(file)?;
+                let traces = rules.explain(&file);
+                let matched = rules.route(&file)?;
+                UI::render_rule_test(&RoutingRules::path(&root), &file, &traces, matched.as_ref());
+                Ok(())
+            }
+            Command::Usage {
+                path,
+                extensions,
+                min_size,
+                older_than,
+                top,
+            } => {
+                let root = get_drive_root()?;
+                let config = LookerConfig::load(&root)?;
+                let definition = StructureManager::load(
+                    &root,
+                    config.structure.profile_or(args.profile.as_deref()),
+                )?;
+                let filter = UsageFilter {
+                    path: path.clone(),
+                    extensions: extensions
+                        .iter()
+                        .map(|ext| ext.trim_start_matches('.').to_lowercase())
+                        .collect(),
+                    min_size: min_size.as_deref().map(parse_size).transpose()?,
+                    older_than_days: *older_than,
+                };
+                let index = ScanIndex::open(&root, args.rescan);
+                let report = DiskUsage::collect(&root, &definition, &filter, *top, &index)?;
+                save_scan_index(&index);
+                match args.format {
+                    OutputFormat::Text => UI::render_disk_usage(&report, args.verbose),
+                    OutputFormat::Markdown => print!("{}", report.to_markdown()),
+                    OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+                }
+                Ok(())
+            }
+        };
+    }
+
+    // downloads 整理モード
+    if args.organize_downloads {
+        let root = get_drive_root()?;
+        let config = LookerConfig::load(&root)?;
+        let stale_days = args.stale_days.unwrap_or(config.downloads.stale_days);
+        let rules = RoutingRules::load(&root)?;
+        let plan = DownloadManager::plan(&root, stale_days, &rules)?;
+        UI::render_download_plan(&plan, args.verbose);
+
+        if plan.moves.is_empty() {
+            println!("移動が必要なファイルはありません。");
+            return Ok(());
+        }
+        if !(args.apply || args.yes) {
+            println!("\n--apply を付けると、上記の変更を適用します。");
+            return Ok(());
+        }
+        if !args.yes && !confirm("変更を適用しますか？")? {
+            println!("適用をキャンセルしました。");
+            return Ok(());
+        }
+        return RecordManager::apply_moves(&plan.moves);
+    }
+
     // ショートカット作成モード
-    if args.create_shortcuts {
+    if args.create_shortcuts || args.gallery_index {
+        let root = get_drive_root()?;
+        let config = LookerConfig::load(&root)?;
+        let mut options = GalleryOptions::from_config(&config);
+        if let Some(kind) = args.link_mode {
+            options.link_mode = LinkMode::from(kind);
+        }
+        if args.gallery_per_kind {
+            options.per_kind = true;
+        }
+        if let Some(threshold) = args.match_threshold {
+            options.match_threshold = threshold;
+        }
+        if let Some(layout) = args.gallery_layout {
+            options.layout = GalleryLayout::from(layout);
+        }
+        if !args.gallery_views.is_empty() {
+            options.views = args
+                .gallery_views
+                .iter()
+                .map(|view| GalleryView::from(*view))
+                .collect();
+        }
+
+        let plan = GalleryManager::plan(&root, &options)?;
+        UI::render_gallery_plan(&plan, args.verbose);
+        if plan.items.is_empty() {
+            println!("命名規則に従ったプロジェクト成果物が見つかりませんでした。");
+            return Ok(());
+        }
+        if args.create_shortcuts {
+            GalleryManager::apply(&root, &plan, &options)?;
+        }
+        if args.gallery_index {
+            let spinner = UI::loading("サムネイルを生成中...");
+            let index_path = GalleryIndex::write(&plan)?;
+            spinner.finish_and_clear();
+            UI::success(&format!("一覧ページを作成しました: {}", index_path.display()));
+        }
+        return Ok(());
+    }
+
+    // ギャラリーのリンク変換モード
+    if args.migrate_relative_links {
         let root = get_drive_root()?;
-        return GalleryManager::create_shortcuts(&root);
+        let migrations = GalleryManager::plan_relative_link_migration(&root)?;
+        UI::render_link_migrations(&migrations, args.verbose);
+
+        if migrations.is_empty() {
+            println!("変換が必要なリンクはありません。");
+            return Ok(());
+        }
+        if !(args.apply || args.yes) {
+            println!("\n--apply を付けると、上記の変更を適用します。");
+            return Ok(());
+        }
+        if !args.yes && !confirm("変更を適用しますか？")? {
+            println!("適用をキャンセルしました。");
+            return Ok(());
+        }
+        return GalleryManager::migrate_to_relative_links(&root, &migrations);
+    }
+
+    // プロジェクトの命名チェックモード
+    if args.audit_projects {
+        let root = get_drive_root()?;
+        let config = LookerConfig::load(&root)?;
+        let threshold = args
+            .match_threshold
+            .unwrap_or(config.gallery.match_threshold);
+        let report = ProjectAudit::plan(&root, threshold)?;
+        UI::render_project_audit(&report, args.verbose);
+
+        if report.issues.is_empty() {
+            println!("1_projects は命名規則に沿っています。");
+            return Ok(());
+        }
+        if report.renames.is_empty() {
+            return Ok(());
+        }
+        if !(args.apply || args.yes) {
+            println!("\n--apply を付けると、上記の変更を適用します。");
+            return Ok(());
+        }
+        if !args.yes && !confirm("変更を適用しますか？")? {
+            println!("適用をキャンセルしました。");
+            return Ok(());
+        }
+        return ProjectAudit::apply(&report);
+    }
+
+    // 素材フォルダのチェックモード
+    if args.audit_assets {
+        let root = get_drive_root()?;
+        let report = AssetAudit::plan(&root)?;
+        UI::render_asset_audit(&report, args.verbose);
+
+        if report.moves.is_empty() {
+            println!("2_assets は種類とカテゴリに沿っています。");
+            return Ok(());
+        }
+        if !(args.apply || args.yes) {
+            println!("\n--apply を付けると、上記の変更を適用します。");
+            return Ok(());
+        }
+        if !args.yes && !confirm("変更を適用しますか？")? {
+            println!("適用をキャンセルしました。");
+            return Ok(());
+        }
+        return AssetAudit::apply(&report);
+    }
+
+    // フォルダ構造の確認モード（定期的なヘルスチェック用）
+    if args.check_structure {
+        let root = get_drive_root()?;
+        let config = LookerConfig::load(&root)?;
+        let definition =
+            StructureManager::load(&root, config.structure.profile_or(args.profile.as_deref()))?;
+        let result = StructureManager::validate_structure(&root, &definition)?;
+        // 確認だけのモードなので、設定ファイルの指定では書かず --folder-notes のときだけ書く
+        let notes = match args.folder_notes.map(FolderNoteFormat::from) {
+            Some(format) => FolderNotes::sync(&root, &definition, format)?,
+            None => Vec::new(),
+        };
+
+        match args.format {
+            OutputFormat::Text => {
+                UI::render_structure_check(&result, &definition, args.verbose);
+                UI::render_folder_notes(&notes, args.verbose);
+            }
+            OutputFormat::Markdown => print!("{}", result.to_markdown(&definition, &notes)),
+            OutputFormat::Json => {
+                let mut json = serde_json::to_value(&result)?;
+                json["completion_rate"] = result.completion_rate().into();
+                json["complete"] = result.is_complete().into();
+                json["folder_notes"] = serde_json::to_value(&notes)?;
+                println!("{}", serde_json::to_string_pretty(&json)?);
+            }
+        }
+
+        if !result.is_complete() || !result.category_issues.is_empty() {
+            std::process::exit(2);
+        }
+        return Ok(());
+    }
+
+    // ドライブの状態のレポート
+    if args.health_report {
+        let root = get_drive_root()?;
+        let config = LookerConfig::load(&root)?;
+        let definition =
+            StructureManager::load(&root, config.structure.profile_or(args.profile.as_deref()))?;
+        let index = ScanIndex::open(&root, args.rescan);
+        let report = WorkspaceHealth::collect(&root, &config, &definition, &index)?;
+        save_scan_index(&index);
+        match args.format {
+            OutputFormat::Text => UI::render_health_report(&report, args.verbose),
+            OutputFormat::Markdown => print!("{}", report.to_markdown()),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        }
+        return Ok(());
+    }
+
+    // フォルダ構造のずれの確認モード
+    if args.structure_drift {
+        let root = get_drive_root()?;
+        let config = LookerConfig::load(&root)?;
+        let definition =
+            StructureManager::load(&root, config.structure.profile_or(args.profile.as_deref()))?;
+        let report = StructureDrift::plan(&root, &definition)?;
+        UI::render_structure_drift(&report, args.verbose);
+
+        if report.merges.is_empty() {
+            if report.entries.is_empty() {
+                println!("標準フォルダ構造とのずれはありません。");
+            }
+            return Ok(());
+        }
+        if !(args.apply || args.yes) {
+            println!("\n--apply を付けると、上記の変更を適用します。");
+            return Ok(());
+        }
+        if !args.yes && !confirm("変更を適用しますか？")? {
+            println!("適用をキャンセルしました。");
+            return Ok(());
+        }
+        return StructureDrift::apply(&report);
     }
 
     // フォルダ構造作成モード
     if args.ensure_structure {
         let root = get_drive_root()?;
-        return StructureManager::ensure_standard_structure(&root);
+        let config = LookerConfig::load(&root)?;
+        let definition =
+            StructureManager::load(&root, config.structure.profile_or(args.profile.as_deref()))?;
+        StructureManager::ensure_standard_structure(&root, &definition)?;
+        let note_format = args
+            .folder_notes
+            .map(FolderNoteFormat::from)
+            .or(config.structure.folder_notes);
+        if let Some(format) = note_format {
+            let notes = FolderNotes::sync(&root, &definition, format)?;
+            UI::render_folder_notes(&notes, args.verbose);
+        }
+        return Ok(());
     }
 
     // デフォルトの record 整理モード
-    let record_root = if let Some(path) = args.record_path {
-        path
-    } else {
-        auto_detect_record_root()?
-    };
-
+    let record_root = resolve_record_root(args.record_path.as_deref())?;
+    let workspace = workspace_root(&record_root)?;
     let mut options = RecordOptions {
         check_misplaced: !args.fast,
-        ..RecordOptions::default()
+        rules: RoutingRules::load(&workspace)?,
+        ..RecordOptions::new(ScanIndex::open(&workspace, args.rescan))
     };
     if !args.record_types.is_empty() {
         options
@@ -116,6 +701,7 @@ fn run_cli_mode() -> Result<()> {
     }
 
     let plan = RecordManager::plan(&record_root, &options)?;
+    save_scan_index(&options.index);
     UI::render_plan_summary(&plan, args.verbose);
 
     if plan.is_empty() {
@@ -139,6 +725,13 @@ fn run_cli_mode() -> Result<()> {
     Ok(())
 }
 
+/// 走査キャッシュを書き出す（書き込めなくても処理は続ける）
+fn save_scan_index(index: &ScanIndex) {
+    if let Err(err) = index.save() {
+        UI::warning(&format!("{err:#}"));
+    }
+}
+
 fn confirm(prompt: &str) -> Result<bool> {
     print!("{prompt} [y/N]: ");
     io::stdout().flush().ok();
@@ -159,9 +752,42 @@ fn run_interactive_mode() -> Result<()> {
             MenuAction::OrganizeNow => {
                 handle_organize_records()?;
             }
+            MenuAction::OrganizeDownloads => {
+                handle_organize_downloads()?;
+            }
             MenuAction::CreateGalleryShortcuts => {
                 handle_create_gallery_shortcuts()?;
             }
+            MenuAction::CreateGalleryIndex => {
+                handle_create_gallery_index()?;
+            }
+            MenuAction::MigrateRelativeLinks => {
+                handle_migrate_relative_links()?;
+            }
+            MenuAction::NewProject => {
+                handle_new_project()?;
+            }
+            MenuAction::ArchiveProjects => {
+                handle_archive_projects()?;
+            }
+            MenuAction::RestoreArchive => {
+                handle_restore_archive()?;
+            }
+            MenuAction::AuditProjects => {
+                handle_audit_projects()?;
+            }
+            MenuAction::AuditAssets => {
+                handle_audit_assets()?;
+            }
+            MenuAction::StructureDrift => {
+                handle_structure_drift()?;
+            }
+            MenuAction::HealthReport => {
+                handle_health_report()?;
+            }
+            MenuAction::DiskUsage => {
+                handle_disk_usage()?;
+            }
             MenuAction::EnsureStructure => {
                 handle_ensure_structure()?;
             }
@@ -177,14 +803,21 @@ fn run_interactive_mode() -> Result<()> {
 
 fn handle_organize_records() -> Result<()> {
     let record_root = auto_detect_record_root()?;
-    let options = Menu::ask_record_options()?;
+    let workspace = workspace_root(&record_root)?;
+    let rescan = Menu::confirm_rescan()?;
+    let mut options = Menu::ask_record_options(ScanIndex::open(&workspace, rescan))?;
+    options.rules = RoutingRules::load(&workspace)?;
 
     UI::section("Recordフォルダの整理");
     UI::info(&format!("対象: {}", record_root.display()));
+    if !options.rules.is_empty() {
+        UI::info(&format!("振り分けルール: {} 件", options.rules.len()));
+    }
 
     let spinner = UI::loading("フォルダ構造を解析中...");
     let plan = RecordManager::plan(&record_root, &options)?;
     spinner.finish_and_clear();
+    save_scan_index(&options.index);
 
     UI::render_plan_summary(&plan, false);
 
@@ -206,6 +839,41 @@ fn handle_organize_records() -> Result<()> {
     Ok(())
 }
 
+fn handle_organize_downloads() -> Result<()> {
+    UI::section("downloadsフォルダの整理");
+
+    let root = get_drive_root()?;
+    let config = LookerConfig::load(&root)?;
+
+    let rules = RoutingRules::load(&root)?;
+    if !rules.is_empty() {
+        UI::info(&format!("振り分けルール: {} 件", rules.len()));
+    }
+
+    let spinner = UI::loading("downloads を解析中...");
+    let plan = DownloadManager::plan(&root, config.downloads.stale_days, &rules)?;
+    spinner.finish_and_clear();
+
+    UI::render_download_plan(&plan, false);
+
+    if plan.moves.is_empty() {
+        UI::success("移動が必要なファイルはありません。");
+        UI::wait_for_enter("\nEnterでメニューに戻ります...");
+        return Ok(());
+    }
+
+    if Menu::confirm_execution(plan.moves.actions.len())? {
+        UI::section("変更を適用中");
+        RecordManager::apply_moves(&plan.moves)?;
+        UI::wait_for_enter("\n処理が完了しました。Enterでメニューに戻ります...");
+    } else {
+        UI::warning("適用をキャンセルしました。");
+        UI::wait_for_enter("\nEnterでメニューに戻ります...");
+    }
+
+    Ok(())
+}
+
 fn handle_create_gallery_shortcuts() -> Result<()> {
     UI::section("プロジェクト成果物のショートカット作成");
     
@@ -213,19 +881,300 @@ fn handle_create_gallery_shortcuts() -> Result<()> {
     UI::info(&format!("ルートディレクトリ: {}", root.display()));
     UI::info("1_projects 以下のプロジェクト成果物を探索し、5_gallery にショートカットを作成します。\n");
     
-    GalleryManager::create_shortcuts(&root)?;
+    let config = LookerConfig::load(&root)?;
+    let options = GalleryOptions::from_config(&config);
+
+    let spinner = UI::loading("1_projects を探索中...");
+    let plan = GalleryManager::plan(&root, &options)?;
+    spinner.finish_and_clear();
+
+    UI::render_gallery_plan(&plan, false);
+    if plan.items.is_empty() {
+        UI::warning("命名規則に従ったプロジェクト成果物が見つかりませんでした。");
+    } else {
+        GalleryManager::apply(&root, &plan, &options)?;
+    }
     
     UI::wait_for_enter("\n処理が完了しました。Enterでメニューに戻ります...");
     Ok(())
 }
 
+fn handle_create_gallery_index() -> Result<()> {
+    UI::section("ギャラリーの一覧ページ作成");
+
+    let root = get_drive_root()?;
+    UI::info(&format!("ルートディレクトリ: {}", root.display()));
+
+    let config = LookerConfig::load(&root)?;
+    let options = GalleryOptions::from_config(&config);
+
+    let spinner = UI::loading("1_projects を探索中...");
+    let plan = GalleryManager::plan(&root, &options)?;
+    spinner.finish_and_clear();
+    UI::render_gallery_plan(&plan, false);
+
+    let spinner = UI::loading("サムネイルを生成中...");
+    let index_path = GalleryIndex::write(&plan)?;
+    spinner.finish_and_clear();
+    UI::success(&format!("一覧ページを作成しました: {}", index_path.display()));
+
+    UI::wait_for_enter("\n処理が完了しました。Enterでメニューに戻ります...");
+    Ok(())
+}
+
+fn handle_migrate_relative_links() -> Result<()> {
+    UI::section("ギャラリーのリンクを相対パスに変換");
+
+    let root = get_drive_root()?;
+    UI::info(&format!("ルートディレクトリ: {}", root.display()));
+
+    let migrations = GalleryManager::plan_relative_link_migration(&root)?;
+    UI::render_link_migrations(&migrations, false);
+
+    if migrations.is_empty() {
+        UI::success("変換が必要なリンクはありません。");
+        UI::wait_for_enter("\nEnterでメニューに戻ります...");
+        return Ok(());
+    }
+
+    if Menu::confirm_execution(migrations.len())? {
+        UI::section("変更を適用中");
+        GalleryManager::migrate_to_relative_links(&root, &migrations)?;
+        UI::wait_for_enter("\n処理が完了しました。Enterでメニューに戻ります...");
+    } else {
+        UI::warning("適用をキャンセルしました。");
+        UI::wait_for_enter("\nEnterでメニューに戻ります...");
+    }
+
+    Ok(())
+}
+
+fn handle_new_project() -> Result<()> {
+    UI::section("新しいプロジェクトの作成");
+
+    let root = get_drive_root()?;
+    UI::info(&format!("ルートディレクトリ: {}", root.display()));
+
+    let config = LookerConfig::load(&root)?;
+    let (category, name) = Menu::ask_new_project(ProjectScaffold::categories(&root))?;
+    if let Err(err) = ProjectScaffold::create(&root, &category, &name, &config.project) {
+        UI::error(&err.to_string());
+    }
+
+    UI::wait_for_enter("\nEnterでメニューに戻ります...");
+    Ok(())
+}
+
+fn handle_archive_projects() -> Result<()> {
+    UI::section("完了したプロジェクトのアーカイブ");
+
+    let root = get_drive_root()?;
+    UI::info(&format!("ルートディレクトリ: {}", root.display()));
+
+    let days = Menu::ask_archive_days()?;
+    let spinner = UI::loading("1_projects を確認中...");
+    let plan = ProjectArchive::plan(&root, &[], Some(days))?;
+    spinner.finish_and_clear();
+
+    UI::render_archive_plan(&plan, false);
+
+    let count = plan.ready().count();
+    if count == 0 {
+        UI::success("アーカイブするプロジェクトはありません。");
+        UI::wait_for_enter("\nEnterでメニューに戻ります...");
+        return Ok(());
+    }
+
+    if Menu::confirm_execution(count)? {
+        UI::section("アーカイブ中");
+        ProjectArchive::apply(&root, &plan)?;
+        UI::wait_for_enter("\n処理が完了しました。Enterでメニューに戻ります...");
+    } else {
+        UI::warning("適用をキャンセルしました。");
+        UI::wait_for_enter("\nEnterでメニューに戻ります...");
+    }
+
+    Ok(())
+}
+
+fn handle_restore_archive() -> Result<()> {
+    UI::section("アーカイブからの復元");
+
+    let root = get_drive_root()?;
+    UI::info(&format!("ルートディレクトリ: {}", root.display()));
+
+    let archives = ProjectArchive::list(&root)?;
+    UI::render_archive_list(&archives, false);
+    if archives.is_empty() {
+        UI::wait_for_enter("\nEnterでメニューに戻ります...");
+        return Ok(());
+    }
+
+    let (zip_path, manifest) = Menu::select_archive(archives)?;
+    let overwrite = root.join(&manifest.original_path).exists()
+        && Menu::confirm_overwrite(&manifest.original_path)?;
+
+    UI::section("復元中");
+    match ProjectArchive::restore(&root, &zip_path, overwrite) {
+        Ok(_) => UI::info("ギャラリーに戻すには、ショートカットを作成し直してください。"),
+        Err(err) => UI::error(&format!("{:#}", err)),
+    }
+
+    UI::wait_for_enter("\nEnterでメニューに戻ります...");
+    Ok(())
+}
+
+fn handle_audit_projects() -> Result<()> {
+    UI::section("プロジェクトの命名チェック");
+
+    let root = get_drive_root()?;
+    UI::info(&format!("ルートディレクトリ: {}", root.display()));
+
+    let config = LookerConfig::load(&root)?;
+    let spinner = UI::loading("1_projects を確認中...");
+    let report = ProjectAudit::plan(&root, config.gallery.match_threshold)?;
+    spinner.finish_and_clear();
+
+    UI::render_project_audit(&report, false);
+
+    if report.issues.is_empty() {
+        UI::success("1_projects は命名規則に沿っています。");
+        UI::wait_for_enter("\nEnterでメニューに戻ります...");
+        return Ok(());
+    }
+    if report.renames.is_empty() {
+        UI::wait_for_enter("\nEnterでメニューに戻ります...");
+        return Ok(());
+    }
+
+    if Menu::confirm_execution(report.renames.len())? {
+        UI::section("変更を適用中");
+        ProjectAudit::apply(&report)?;
+        UI::wait_for_enter("\n処理が完了しました。Enterでメニューに戻ります...");
+    } else {
+        UI::warning("適用をキャンセルしました。");
+        UI::wait_for_enter("\nEnterでメニューに戻ります...");
+    }
+
+    Ok(())
+}
+
+fn handle_audit_assets() -> Result<()> {
+    UI::section("素材フォルダのチェック");
+
+    let root = get_drive_root()?;
+    UI::info(&format!("ルートディレクトリ: {}", root.display()));
+
+    let spinner = UI::loading("2_assets を確認中...");
+    let report = AssetAudit::plan(&root)?;
+    spinner.finish_and_clear();
+
+    UI::render_asset_audit(&report, false);
+
+    if report.moves.is_empty() {
+        if report.issues.is_empty() {
+            UI::success("2_assets は種類とカテゴリに沿っています。");
+        }
+        UI::wait_for_enter("\nEnterでメニューに戻ります...");
+        return Ok(());
+    }
+
+    if Menu::confirm_execution(report.moves.actions.len())? {
+        UI::section("変更を適用中");
+        AssetAudit::apply(&report)?;
+        UI::wait_for_enter("\n処理が完了しました。Enterでメニューに戻ります...");
+    } else {
+        UI::warning("適用をキャンセルしました。");
+        UI::wait_for_enter("\nEnterでメニューに戻ります...");
+    }
+
+    Ok(())
+}
+
+fn handle_structure_drift() -> Result<()> {
+    UI::section("フォルダ構造のずれの確認");
+
+    let root = get_drive_root()?;
+    let config = LookerConfig::load(&root)?;
+    let definition = StructureManager::load(&root, config.structure.profile_or(None))?;
+
+    let spinner = UI::loading("フォルダ構造を確認中...");
+    let report = StructureDrift::plan(&root, &definition)?;
+    spinner.finish_and_clear();
+
+    UI::render_structure_drift(&report, false);
+
+    if report.merges.is_empty() {
+        if report.entries.is_empty() {
+            UI::success("標準フォルダ構造とのずれはありません。");
+        }
+        UI::wait_for_enter("\nEnterでメニューに戻ります...");
+        return Ok(());
+    }
+
+    if Menu::confirm_execution(report.merges.len())? {
+        UI::section("変更を適用中");
+        StructureDrift::apply(&report)?;
+        UI::wait_for_enter("\n処理が完了しました。Enterでメニューに戻ります...");
+    } else {
+        UI::warning("適用をキャンセルしました。");
+        UI::wait_for_enter("\nEnterでメニューに戻ります...");
+    }
+
+    Ok(())
+}
+
+fn handle_health_report() -> Result<()> {
+    UI::section("ドライブの状態");
+
+    let root = get_drive_root()?;
+    let config = LookerConfig::load(&root)?;
+    let definition = StructureManager::load(&root, config.structure.profile_or(None))?;
+
+    let rescan = Menu::confirm_rescan()?;
+    let spinner = UI::loading("ドライブの状態を集計中...");
+    let index = ScanIndex::open(&root, rescan);
+    let report = WorkspaceHealth::collect(&root, &config, &definition, &index)?;
+    spinner.finish_and_clear();
+    save_scan_index(&index);
+
+    UI::render_health_report(&report, false);
+    UI::wait_for_enter("\nEnterでメニューに戻ります...");
+    Ok(())
+}
+
+fn handle_disk_usage() -> Result<()> {
+    UI::section("ディスク使用量");
+
+    let root = get_drive_root()?;
+    let config = LookerConfig::load(&root)?;
+    let definition = StructureManager::load(&root, config.structure.profile_or(None))?;
+
+    let rescan = Menu::confirm_rescan()?;
+    let spinner = UI::loading("ファイルのサイズを集計中...");
+    let index = ScanIndex::open(&root, rescan);
+    let report = DiskUsage::collect(&root, &definition, &UsageFilter::default(), 20, &index)?;
+    spinner.finish_and_clear();
+    save_scan_index(&index);
+
+    UI::render_disk_usage(&report, false);
+    UI::wait_for_enter("\nEnterでメニューに戻ります...");
+    Ok(())
+}
+
 fn handle_ensure_structure() -> Result<()> {
     UI::section("標準フォルダ構造の確認と作成");
     
     let root = get_drive_root()?;
     UI::info("標準フォルダ構造に従って、不足しているフォルダを自動作成します。\n");
-    
-    StructureManager::ensure_standard_structure(&root)?;
+
+    let config = LookerConfig::load(&root)?;
+    let definition = StructureManager::load(&root, config.structure.profile_or(None))?;
+    StructureManager::ensure_standard_structure(&root, &definition)?;
+    if let Some(format) = config.structure.folder_notes {
+        let notes = FolderNotes::sync(&root, &definition, format)?;
+        UI::render_folder_notes(&notes, false);
+    }
     
     UI::wait_for_enter("\n処理が完了しました。Enterでメニューに戻ります...");
     Ok(())
@@ -240,6 +1189,27 @@ fn get_drive_root() -> Result<PathBuf> {
     Ok(root)
 }
 
+/// `--record-path` の指定、無ければ自動検出した record フォルダ
+fn resolve_record_root(record_path: Option<&Path>) -> Result<PathBuf> {
+    match record_path {
+        Some(path) => Ok(path.to_path_buf()),
+        None => auto_detect_record_root(),
+    }
+}
+
+/// record フォルダの位置からルートを決める（`<root>/0_inbox/record` 以外はドライブのルート）
+fn workspace_root(record_root: &Path) -> Result<PathBuf> {
+    if let Some(inbox) = record_root.parent()
+        && inbox
+            .file_name()
+            .is_some_and(|name| name.eq_ignore_ascii_case("0_inbox"))
+        && let Some(root) = inbox.parent()
+    {
+        return Ok(root.to_path_buf());
+    }
+    get_drive_root()
+}
+
 /// 現在のドライブのルートから辿って record フォルダを検出する
 fn auto_detect_record_root() -> Result<PathBuf> {
     let current = std::env::current_dir()?;
@@ -1,14 +1,28 @@
 use std::fmt;
+use std::path::PathBuf;
 
 use anyhow::Result;
-use inquire::{Confirm, Select};
+use inquire::{Confirm, Select, Text};
 
+use crate::project_archive::ArchiveManifest;
 use crate::record_manager::RecordOptions;
+use crate::scan_index::ScanIndex;
 
 #[derive(Clone, Copy)]
 pub enum MenuAction {
     OrganizeNow,
+    OrganizeDownloads,
     CreateGalleryShortcuts,
+    CreateGalleryIndex,
+    MigrateRelativeLinks,
+    NewProject,
+    ArchiveProjects,
+    RestoreArchive,
+    AuditProjects,
+    AuditAssets,
+    StructureDrift,
+    HealthReport,
+    DiskUsage,
     EnsureStructure,
     Exit,
 }
@@ -34,10 +48,54 @@ impl Menu {
                 label: "Recordフォルダを整理（プレビュー後に適用）",
                 action: MenuAction::OrganizeNow,
             },
+            MenuChoice {
+                label: "downloadsフォルダを整理（プレビュー後に適用）",
+                action: MenuAction::OrganizeDownloads,
+            },
             MenuChoice {
                 label: "プロジェクト成果物のショートカットを作成",
                 action: MenuAction::CreateGalleryShortcuts,
             },
+            MenuChoice {
+                label: "ギャラリーの一覧ページ（index.html）を作成",
+                action: MenuAction::CreateGalleryIndex,
+            },
+            MenuChoice {
+                label: "ギャラリーのリンクを相対パスに変換",
+                action: MenuAction::MigrateRelativeLinks,
+            },
+            MenuChoice {
+                label: "新しいプロジェクトを作成",
+                action: MenuAction::NewProject,
+            },
+            MenuChoice {
+                label: "完了したプロジェクトをアーカイブ",
+                action: MenuAction::ArchiveProjects,
+            },
+            MenuChoice {
+                label: "アーカイブからプロジェクトを復元",
+                action: MenuAction::RestoreArchive,
+            },
+            MenuChoice {
+                label: "プロジェクトの命名をチェック",
+                action: MenuAction::AuditProjects,
+            },
+            MenuChoice {
+                label: "素材フォルダ（2_assets）をチェック",
+                action: MenuAction::AuditAssets,
+            },
+            MenuChoice {
+                label: "フォルダ構造のずれをチェック",
+                action: MenuAction::StructureDrift,
+            },
+            MenuChoice {
+                label: "ドライブの状態を確認",
+                action: MenuAction::HealthReport,
+            },
+            MenuChoice {
+                label: "ディスク使用量を確認",
+                action: MenuAction::DiskUsage,
+            },
             MenuChoice {
                 label: "標準フォルダ構造を確認・作成",
                 action: MenuAction::EnsureStructure,
@@ -57,8 +115,76 @@ impl Menu {
     }
 
     /// 余計な選択肢は廃止し、デフォルト設定のみ使用する
-    pub fn ask_record_options() -> Result<RecordOptions> {
-        Ok(RecordOptions::default())
+    pub fn ask_record_options(index: ScanIndex) -> Result<RecordOptions> {
+        Ok(RecordOptions::new(index))
+    }
+
+    /// 新しいプロジェクトのカテゴリと名前を尋ねる
+    pub fn ask_new_project(categories: Vec<String>) -> Result<(String, String)> {
+        const NEW_CATEGORY: &str = "（新しいカテゴリを作成）";
+
+        let mut options = categories;
+        options.push(NEW_CATEGORY.to_string());
+        let mut category = Select::new("カテゴリを選択してください", options)
+            .prompt()
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        if category == NEW_CATEGORY {
+            category = Text::new("新しいカテゴリ名:")
+                .prompt()
+                .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        }
+
+        let name = Text::new("プロジェクト名（日付は自動で付きます）:")
+            .prompt()
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        Ok((category.trim().to_string(), name.trim().to_string()))
+    }
+
+    /// アーカイブの対象にする経過日数を尋ねる
+    pub fn ask_archive_days() -> Result<u64> {
+        let input = Text::new("最終更新から何日以上経ったプロジェクトをアーカイブしますか？")
+            .with_default("365")
+            .prompt()
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        input
+            .trim()
+            .parse()
+            .map_err(|_| anyhow::anyhow!("日数は整数で入力してください: {}", input))
+    }
+
+    /// 復元するアーカイブを選ぶ
+    pub fn select_archive(
+        archives: Vec<(PathBuf, ArchiveManifest)>,
+    ) -> Result<(PathBuf, ArchiveManifest)> {
+        let labels: Vec<String> = archives
+            .iter()
+            .map(|(_, manifest)| format!("{}/{}", manifest.category, manifest.project))
+            .collect();
+        let selected = Select::new("復元するアーカイブを選択してください", labels)
+            .raw_prompt()
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        archives
+            .into_iter()
+            .nth(selected.index)
+            .ok_or_else(|| anyhow::anyhow!("アーカイブの選択に失敗しました"))
+    }
+
+    /// 元の場所に既にフォルダがあるときに上書きするか尋ねる
+    pub fn confirm_overwrite(path: &str) -> Result<bool> {
+        Confirm::new(&format!("{} は既に存在します。上書きして展開しますか？", path))
+            .with_default(false)
+            .prompt()
+            .map_err(|e| anyhow::anyhow!(e.to_string()))
+    }
+
+    /// 走査キャッシュを使わずに読み直すか尋ねる（コマンドの `--rescan` と同じ）
+    pub fn confirm_rescan() -> Result<bool> {
+        Confirm::new("走査キャッシュを使わずにすべてのフォルダを読み直しますか？")
+            .with_default(false)
+            .with_help_message("上書きしたファイルのサイズや更新日時が反映されないときは「はい」")
+            .prompt()
+            .map_err(|e| anyhow::anyhow!(e.to_string()))
     }
 
     pub fn confirm_execution(action_count: usize) -> Result<bool> {
@@ -1,4 +1,34 @@
+use std::fmt;
+
+use chrono::NaiveDate;
 use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
+
+/// プロジェクトフォルダ名（`YYYYMMDD_name`）の問題
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectNameError {
+    /// 先頭に `YYYYMMDD_` が無い
+    MissingDate,
+    /// 実在しない日付
+    InvalidDate(String),
+    /// 日付の後ろに名前が無い
+    EmptyName,
+    /// 使えない文字を含む
+    InvalidCharacters(String),
+}
+
+impl fmt::Display for ProjectNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProjectNameError::MissingDate => write!(f, "先頭に YYYYMMDD_ の日付がありません"),
+            ProjectNameError::InvalidDate(date) => write!(f, "実在しない日付です: {}", date),
+            ProjectNameError::EmptyName => write!(f, "日付の後ろに名前がありません"),
+            ProjectNameError::InvalidCharacters(chars) => {
+                write!(f, "使えない文字が含まれています: {}", chars)
+            }
+        }
+    }
+}
 
 /// ファイル名の命名規則チェック
 pub struct NamingRule;
@@ -13,5 +43,172 @@ impl NamingRule {
         let re = Regex::new(pattern).unwrap();
         re.is_match(filename)
     }
+
+    /// `YYYYMMDD_name` を日付と名前に分ける（実在する日付のみ、拡張子は含めない）
+    pub fn split_dated_name(name: &str) -> Option<(String, String)> {
+        let (date, rest) = name.split_once('_')?;
+        if date.len() != 8
+            || !date.chars().all(|c| c.is_ascii_digit())
+            || NaiveDate::parse_from_str(date, "%Y%m%d").is_err()
+        {
+            return None;
+        }
+        Some((date.to_string(), rest.to_string()))
+    }
+
+    /// プロジェクトフォルダとして扱う名前なら日付と名前を返す（名前の文字は問わない）
+    ///
+    /// ギャラリー・ヘルスチェック・プロジェクトの作成で共通の判定。
+    /// 名前が命名規則に沿っているかは `check_project_folder_name` で確認する。
+    pub fn project_folder_name(name: &str) -> Option<(String, String)> {
+        Self::split_dated_name(name).filter(|(_, project)| !project.is_empty())
+    }
+
+    /// プロジェクト名（日付を除いた部分）に使える文字: 文字・数字・`-`・`_`
+    pub fn is_project_name_char(c: char) -> bool {
+        c.is_alphanumeric() || matches!(c, '-' | '_')
+    }
+
+    /// プロジェクト名（日付を除いた部分）のチェック
+    pub fn check_project_name(name: &str) -> Result<(), ProjectNameError> {
+        if name.is_empty() {
+            return Err(ProjectNameError::EmptyName);
+        }
+        let mut invalid = String::new();
+        for c in name.chars().filter(|c| !Self::is_project_name_char(*c)) {
+            if !invalid.contains(c) {
+                invalid.push(c);
+            }
+        }
+        if !invalid.is_empty() {
+            return Err(ProjectNameError::InvalidCharacters(format!("{:?}", invalid)));
+        }
+        Ok(())
+    }
+
+    /// プロジェクトフォルダ名の想定フォーマット: YYYYMMDD_name
+    ///
+    /// 成功すると日付と名前を返す。
+    pub fn check_project_folder_name(name: &str) -> Result<(String, String), ProjectNameError> {
+        let Some((date, project)) = name.split_once('_') else {
+            return Err(ProjectNameError::MissingDate);
+        };
+        if date.len() != 8 || !date.chars().all(|c| c.is_ascii_digit()) {
+            return Err(ProjectNameError::MissingDate);
+        }
+        if NaiveDate::parse_from_str(date, "%Y%m%d").is_err() {
+            return Err(ProjectNameError::InvalidDate(date.to_string()));
+        }
+        Self::check_project_name(project)?;
+        Ok((date.to_string(), project.to_string()))
+    }
+
+    /// 命名規則に沿ったプロジェクトフォルダ名の候補（直せない場合や変更不要なら None）
+    ///
+    /// `2024-03-01 name` のような区切り付きの日付は `20240301` に直し、
+    /// 日付が無い・実在しない場合は `fallback_date` を使う。
+    pub fn suggest_project_folder_name(name: &str, fallback_date: &str) -> Option<String> {
+        let normalized: String = name.nfkc().collect();
+        let re = Regex::new(r"^(\d{4})[-._ /]?(\d{2})[-._ /]?(\d{2})(?:[-_ ]+|$)(.*)$").unwrap();
+
+        let (date, rest) = match re.captures(&normalized) {
+            Some(caps) => {
+                let date = format!("{}{}{}", &caps[1], &caps[2], &caps[3]);
+                let date = if NaiveDate::parse_from_str(&date, "%Y%m%d").is_ok() {
+                    date
+                } else {
+                    fallback_date.to_string()
+                };
+                (date, caps[4].to_string())
+            }
+            None => (fallback_date.to_string(), normalized.clone()),
+        };
+
+        let project = Self::sanitize_project_name(&rest)?;
+        let suggestion = format!("{}_{}", date, project);
+        (suggestion != name).then_some(suggestion)
+    }
+
+    /// 使えない文字を `-` に置き換えたプロジェクト名（何も残らなければ None）
+    pub fn sanitize_project_name(name: &str) -> Option<String> {
+        let mut sanitized = String::new();
+        for c in name.nfkc() {
+            if Self::is_project_name_char(c) {
+                sanitized.push(c);
+            } else if !sanitized.ends_with(['-', '_']) {
+                sanitized.push('-');
+            }
+        }
+        let sanitized = sanitized.trim_matches(['-', '_']);
+        (!sanitized.is_empty()).then(|| sanitized.to_string())
+    }
+
+    /// 名前の表記ゆれを吸収した比較用の文字列を作る
+    ///
+    /// Unicode NFKC で全角英数字や半角カナを揃え、小文字化したうえで
+    /// 空白・ハイフン・アンダースコア・ドット・中黒を取り除く。
+    pub fn normalize_name(name: &str) -> String {
+        name.nfkc()
+            .flat_map(char::to_lowercase)
+            .filter(|c| !c.is_whitespace() && !matches!(c, '-' | '_' | '.' | '・' | '‐' | '–' | '—'))
+            .collect()
+    }
+
+    /// 正規化した名前同士の一致度（0.0〜1.0、編集距離ベース）
+    ///
+    /// `a` のような短い名前が長いフォルダ名に含まれるだけでは高い値にならない。
+    pub fn name_similarity(a: &str, b: &str) -> f64 {
+        let a: Vec<char> = Self::normalize_name(a).chars().collect();
+        let b: Vec<char> = Self::normalize_name(b).chars().collect();
+        if a.is_empty() || b.is_empty() {
+            return 0.0;
+        }
+        if a == b {
+            return 1.0;
+        }
+
+        // レーベンシュタイン距離（1 行分の DP）
+        let mut row: Vec<usize> = (0..=b.len()).collect();
+        for (i, ca) in a.iter().enumerate() {
+            let mut previous = row[0];
+            row[0] = i + 1;
+            for (j, cb) in b.iter().enumerate() {
+                let current = row[j + 1];
+                row[j + 1] = if ca == cb {
+                    previous
+                } else {
+                    1 + previous.min(row[j]).min(current)
+                };
+                previous = current;
+            }
+        }
+
+        let distance = row[b.len()] as f64;
+        1.0 - distance / a.len().max(b.len()) as f64
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_folder_name_accepts_names_outside_the_naming_rule() {
+        assert_eq!(
+            NamingRule::project_folder_name("20240301_My Project"),
+            Some(("20240301".to_string(), "My Project".to_string()))
+        );
+        assert!(NamingRule::check_project_folder_name("20240301_My Project").is_err());
+        assert_eq!(
+            NamingRule::project_folder_name("20240301_v1.2"),
+            Some(("20240301".to_string(), "v1.2".to_string()))
+        );
+    }
+
+    #[test]
+    fn project_folder_name_rejects_missing_parts() {
+        assert_eq!(NamingRule::project_folder_name("20240301_"), None);
+        assert_eq!(NamingRule::project_folder_name("20241301_promo"), None);
+        assert_eq!(NamingRule::project_folder_name("film"), None);
+    }
+}
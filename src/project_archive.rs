@@ -0,0 +1,764 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{Datelike, Timelike};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::gallery_link::portable_path;
+use crate::gallery_manager::GalleryManager;
+use crate::project_scaffold::ProjectScaffold;
+use crate::ui::UI;
+
+/// 完了したプロジェクトの 9_archive への保存
+pub struct ProjectArchive;
+
+/// zip 内に保存するアーカイブの記録
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    /// プロジェクトフォルダ名（`YYYYMMDD_name`）
+    pub project: String,
+    pub category: String,
+    /// ルートからの元の場所（`/` 区切り）
+    pub original_path: String,
+    /// アーカイブした日時（RFC 3339）
+    pub archived_at: String,
+    /// プロジェクトフォルダからの相対パス（`/` 区切り）
+    pub directories: Vec<String>,
+    pub files: Vec<ArchivedFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedFile {
+    /// プロジェクトフォルダからの相対パス（`/` 区切り）
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+    /// 更新日時（UNIX 時刻、秒）
+    pub modified: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ArchiveItem {
+    pub project_dir: PathBuf,
+    pub category: String,
+    pub name: String,
+    pub archive_path: PathBuf,
+    pub files: usize,
+    pub size: u64,
+    pub last_modified: Option<SystemTime>,
+    /// アーカイブできない理由（あればスキップ）
+    pub skip_reason: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct ArchivePlan {
+    pub archive_dir: PathBuf,
+    pub items: Vec<ArchiveItem>,
+}
+
+impl ArchivePlan {
+    pub fn ready(&self) -> impl Iterator<Item = &ArchiveItem> {
+        self.items.iter().filter(|item| item.skip_reason.is_none())
+    }
+}
+
+/// zip 内の記録のファイル名
+const MANIFEST_NAME: &str = "looker-archive.json";
+
+/// 圧縮しても小さくならない形式（そのまま格納する）
+const STORED_EXTENSIONS: [&str; 16] = [
+    "mp4", "mov", "mkv", "webm", "avi", "jpg", "jpeg", "png", "gif", "webp", "mp3", "aac",
+    "ogg", "flac", "zip", "7z",
+];
+
+impl ProjectArchive {
+    /// アーカイブするプロジェクトを選ぶ（ファイルは変更しない）
+    ///
+    /// `projects` は `category/YYYYMMDD_name` かフォルダ名。省略した場合はすべてのプロジェクトが対象。
+    /// `older_than_days` を指定すると、最終更新からその日数以上経ったものだけに絞る。
+    pub fn plan(root: &Path, projects: &[String], older_than_days: Option<u64>) -> Result<ArchivePlan> {
+        if projects.is_empty() && older_than_days.is_none() {
+            bail!("アーカイブするプロジェクトか、経過日数（--older-than）を指定してください");
+        }
+
+        let all = Self::list_projects(root);
+        let selected: Vec<(String, PathBuf)> = if projects.is_empty() {
+            all
+        } else {
+            projects
+                .iter()
+                .map(|name| Self::find_project(&all, name))
+                .collect::<Result<_>>()?
+        };
+
+        let archive_dir = root.join("9_archive").join("projects");
+        // 大きすぎる日数で桁あふれしたり、時刻の範囲外になったりしないように確かめる
+        let cutoff = older_than_days
+            .map(|days| {
+                days.checked_mul(24 * 60 * 60)
+                    .and_then(|secs| SystemTime::now().checked_sub(Duration::from_secs(secs)))
+                    .with_context(|| format!("経過日数が大きすぎます: {}", days))
+            })
+            .transpose()?;
+
+        let mut items = Vec::new();
+        for (category, project_dir) in selected {
+            let name = file_name(&project_dir);
+            let mut item = ArchiveItem {
+                archive_path: archive_dir.join(&category).join(format!("{}.zip", name)),
+                project_dir,
+                category,
+                name,
+                files: 0,
+                size: 0,
+                last_modified: None,
+                skip_reason: None,
+            };
+
+            for entry in WalkDir::new(&item.project_dir).follow_links(false) {
+                let entry = entry?;
+                let metadata = entry.metadata()?;
+                if entry.path_is_symlink() {
+                    item.skip_reason = Some(format!(
+                        "シンボリックリンクを含みます: {}",
+                        entry.path().display()
+                    ));
+                }
+                if metadata.is_file() {
+                    item.files += 1;
+                    item.size += metadata.len();
+                }
+                if let Ok(modified) = metadata.modified() {
+                    item.last_modified = item.last_modified.max(Some(modified));
+                }
+            }
+
+            if let (Some(cutoff), Some(modified)) = (cutoff, item.last_modified)
+                && modified > cutoff
+            {
+                continue;
+            }
+            if item.archive_path.exists() {
+                item.skip_reason = Some(format!(
+                    "アーカイブが既に存在します: {}",
+                    item.archive_path.display()
+                ));
+            }
+            items.push(item);
+        }
+
+        Ok(ArchivePlan { archive_dir, items })
+    }
+
+    /// 計画に従って zip にまとめ、検証してから元のフォルダとギャラリーのエントリを削除する
+    pub fn apply(root: &Path, plan: &ArchivePlan) -> Result<()> {
+        let mut archived = 0usize;
+        let mut failed = 0usize;
+        let mut gallery_removed = 0usize;
+
+        for item in plan.ready() {
+            UI::info(&format!("アーカイブ中: {}/{}", item.category, item.name));
+            match Self::archive_project(root, item) {
+                Ok(removed) => {
+                    archived += 1;
+                    gallery_removed += removed.len();
+                    for link in removed {
+                        UI::info(&format!("  ギャラリーから削除: {}", link));
+                    }
+                    UI::info(&format!("  作成: {}", item.archive_path.display()));
+                }
+                Err(err) => {
+                    failed += 1;
+                    UI::error(&format!("  {:#}", err));
+                }
+            }
+        }
+
+        UI::success(&format!(
+            "\nアーカイブ完了: {} 件（失敗 {} 件）、ギャラリーのエントリ {} 件を削除",
+            archived, failed, gallery_removed
+        ));
+        if gallery_removed > 0 && root.join("5_gallery").join("index.html").exists() {
+            UI::info("一覧ページ（index.html）を更新するには --gallery-index を実行してください。");
+        }
+        Ok(())
+    }
+
+    /// 1 件分: zip の作成 → 読み直して検証 → ギャラリーの更新 → 元のフォルダの削除
+    fn archive_project(root: &Path, item: &ArchiveItem) -> Result<Vec<String>> {
+        if let Some(parent) = item.archive_path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("フォルダの作成に失敗: {}", parent.display()))?;
+        }
+
+        // 検証が終わるまでは一時ファイルに書く
+        let temp_path = item.archive_path.with_extension("zip.tmp");
+        let result = Self::write_zip(root, item, &temp_path).and_then(|manifest| {
+            Self::verify(&temp_path, &manifest)?;
+            fs::rename(&temp_path, &item.archive_path).with_context(|| {
+                format!("アーカイブの保存に失敗: {}", item.archive_path.display())
+            })
+        });
+        if let Err(err) = result {
+            let _ = fs::remove_file(&temp_path);
+            return Err(err);
+        }
+
+        let removed = GalleryManager::remove_entries_for(root, &item.project_dir)?;
+        fs::remove_dir_all(&item.project_dir).with_context(|| {
+            format!("元のフォルダの削除に失敗: {}", item.project_dir.display())
+        })?;
+        Ok(removed)
+    }
+
+    fn write_zip(root: &Path, item: &ArchiveItem, zip_path: &Path) -> Result<ArchiveManifest> {
+        let file = File::create(zip_path)
+            .with_context(|| format!("アーカイブの作成に失敗: {}", zip_path.display()))?;
+        let mut zip = ZipWriter::new(BufWriter::new(file));
+
+        let mut manifest = ArchiveManifest {
+            project: item.name.clone(),
+            category: item.category.clone(),
+            original_path: portable_path(item.project_dir.strip_prefix(root).unwrap_or(&item.project_dir)),
+            archived_at: chrono::Local::now().to_rfc3339(),
+            directories: Vec::new(),
+            files: Vec::new(),
+        };
+
+        let mut entries: Vec<_> = WalkDir::new(&item.project_dir)
+            .follow_links(false)
+            .min_depth(1)
+            .into_iter()
+            .collect::<std::result::Result<_, _>>()?;
+        entries.sort_by(|a, b| a.path().cmp(b.path()));
+
+        for entry in entries {
+            let relative = portable_path(entry.path().strip_prefix(&item.project_dir)?);
+            let zip_name = format!("{}/{}", item.name, relative);
+
+            if entry.file_type().is_dir() {
+                zip.add_directory(zip_name, SimpleFileOptions::default())?;
+                manifest.directories.push(relative);
+                continue;
+            }
+            if !entry.file_type().is_file() {
+                bail!("通常のファイルではありません: {}", entry.path().display());
+            }
+
+            let metadata = entry.metadata()?;
+            let modified = metadata.modified().ok();
+            let mut options = SimpleFileOptions::default()
+                .compression_method(Self::compression_for(entry.path()))
+                .large_file(metadata.len() >= u32::MAX as u64);
+            if let Some(time) = modified.and_then(zip_time) {
+                options = options.last_modified_time(time);
+            }
+            zip.start_file(zip_name, options)?;
+
+            let mut reader = BufReader::new(
+                File::open(entry.path())
+                    .with_context(|| format!("ファイルを開けません: {}", entry.path().display()))?,
+            );
+            let (size, sha256) = hash_copy(&mut reader, &mut zip)
+                .with_context(|| format!("圧縮に失敗: {}", entry.path().display()))?;
+
+            manifest.files.push(ArchivedFile {
+                path: relative,
+                size,
+                sha256,
+                modified: modified.and_then(unix_seconds),
+            });
+        }
+
+        zip.start_file(
+            MANIFEST_NAME,
+            SimpleFileOptions::default().compression_method(CompressionMethod::Deflated),
+        )?;
+        zip.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+
+        let writer = zip.finish()?;
+        writer
+            .into_inner()
+            .map_err(|err| anyhow!(err.to_string()))?
+            .sync_all()?;
+        Ok(manifest)
+    }
+
+    /// zip を読み直して、記録とすべてのファイルのチェックサムを照合する
+    fn verify(zip_path: &Path, expected: &ArchiveManifest) -> Result<()> {
+        let (manifest, mut archive) = Self::open(zip_path)?;
+        if &manifest != expected {
+            bail!("アーカイブの記録が一致しません: {}", zip_path.display());
+        }
+
+        for file in &manifest.files {
+            let name = format!("{}/{}", manifest.project, file.path);
+            let mut entry = archive
+                .by_name(&name)
+                .with_context(|| format!("アーカイブにファイルがありません: {}", name))?;
+            let (size, sha256) = hash_copy(&mut entry, &mut io::sink())?;
+            if size != file.size || sha256 != file.sha256 {
+                bail!("チェックサムが一致しません: {}", name);
+            }
+        }
+        Ok(())
+    }
+
+    /// 9_archive/projects 以下のアーカイブと記録の一覧（記録を読めないものは警告して除く）
+    pub fn list(root: &Path) -> Result<Vec<(PathBuf, ArchiveManifest)>> {
+        let archive_dir = root.join("9_archive").join("projects");
+        if !archive_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut archives = Vec::new();
+        for entry in WalkDir::new(&archive_dir)
+            .follow_links(false)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            let path = entry.path();
+            let is_zip = path
+                .extension()
+                .map(|ext| ext.eq_ignore_ascii_case("zip"))
+                .unwrap_or(false);
+            if !entry.file_type().is_file() || !is_zip {
+                continue;
+            }
+            match Self::open(path) {
+                Ok((manifest, _)) => archives.push((path.to_path_buf(), manifest)),
+                Err(err) => UI::warning(&format!("{:#}", err)),
+            }
+        }
+        Ok(archives)
+    }
+
+    /// zip のパス、`category/YYYYMMDD_name` またはフォルダ名でアーカイブを探す
+    pub fn find_archive(root: &Path, name: &str) -> Result<PathBuf> {
+        let path = Path::new(name);
+        if path.is_file() {
+            return Ok(path.to_path_buf());
+        }
+
+        let name = name.trim_end_matches(".zip").replace('\\', "/");
+        let archives = Self::list(root)?;
+        let matches: Vec<&PathBuf> = archives
+            .iter()
+            .filter(|(_, manifest)| {
+                name == manifest.project || name == format!("{}/{}", manifest.category, manifest.project)
+            })
+            .map(|(path, _)| path)
+            .collect();
+        match matches.as_slice() {
+            [found] => Ok((*found).clone()),
+            [] => Err(anyhow!("アーカイブが見つかりません: {}", name)),
+            _ => Err(anyhow!(
+                "同じ名前のアーカイブが複数あります（category/name で指定してください）: {}",
+                name
+            )),
+        }
+    }
+
+    /// アーカイブを記録された元の場所に展開し、展開後のチェックサムを照合する
+    ///
+    /// 元の場所に既にフォルダがある場合は `overwrite` を指定しない限り展開しない。
+    pub fn restore(root: &Path, zip_path: &Path, overwrite: bool) -> Result<PathBuf> {
+        let (manifest, mut archive) = Self::open(zip_path)?;
+        let original = safe_relative(&manifest.original_path)?;
+        let target = root.join(original);
+
+        let exists = target.exists();
+        if exists && !overwrite {
+            bail!(
+                "元の場所に既にフォルダがあります（上書きする場合は --force）: {}",
+                target.display()
+            );
+        }
+
+        // 上書きする場合も含めて一時フォルダに展開し、照合できてから入れ替える
+        // （途中で失敗しても元のフォルダは変わらない）
+        let extract_dir = target.with_file_name(format!("{}.restoring", manifest.project));
+        if extract_dir.exists() {
+            // 前回失敗したときの残り
+            fs::remove_dir_all(&extract_dir)
+                .with_context(|| format!("フォルダの削除に失敗: {}", extract_dir.display()))?;
+        }
+        let result = Self::extract(&manifest, &mut archive, &extract_dir)
+            .and_then(|_| Self::verify_extracted(&manifest, &extract_dir));
+        if let Err(err) = result {
+            let _ = fs::remove_dir_all(&extract_dir);
+            return Err(err);
+        }
+
+        if exists {
+            // 元のフォルダを脇に移してから入れ替え、入れ替えられたら削除する
+            let replaced = target.with_file_name(format!("{}.replaced", manifest.project));
+            fs::rename(&target, &replaced)
+                .with_context(|| format!("フォルダ名の変更に失敗: {}", target.display()))?;
+            if let Err(err) = fs::rename(&extract_dir, &target) {
+                let _ = fs::rename(&replaced, &target);
+                return Err(err)
+                    .with_context(|| format!("フォルダ名の変更に失敗: {}", target.display()));
+            }
+            fs::remove_dir_all(&replaced)
+                .with_context(|| format!("元のフォルダの削除に失敗: {}", replaced.display()))?;
+        } else {
+            fs::rename(&extract_dir, &target)
+                .with_context(|| format!("フォルダ名の変更に失敗: {}", target.display()))?;
+        }
+
+        UI::success(&format!(
+            "復元しました: {}（{} ファイル）",
+            target.display(),
+            manifest.files.len()
+        ));
+        Ok(target)
+    }
+
+    fn extract(
+        manifest: &ArchiveManifest,
+        archive: &mut ZipArchive<BufReader<File>>,
+        dir: &Path,
+    ) -> Result<()> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("フォルダの作成に失敗: {}", dir.display()))?;
+        for directory in &manifest.directories {
+            let path = dir.join(safe_relative(directory)?);
+            fs::create_dir_all(&path)
+                .with_context(|| format!("フォルダの作成に失敗: {}", path.display()))?;
+        }
+
+        for file in &manifest.files {
+            let path = dir.join(safe_relative(&file.path)?);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("フォルダの作成に失敗: {}", parent.display()))?;
+            }
+
+            let name = format!("{}/{}", manifest.project, file.path);
+            let mut entry = archive
+                .by_name(&name)
+                .with_context(|| format!("アーカイブにファイルがありません: {}", name))?;
+            let mut writer = BufWriter::new(
+                File::create(&path)
+                    .with_context(|| format!("ファイルの作成に失敗: {}", path.display()))?,
+            );
+            io::copy(&mut entry, &mut writer)
+                .with_context(|| format!("展開に失敗: {}", path.display()))?;
+            let output = writer.into_inner().map_err(|err| anyhow!(err.to_string()))?;
+
+            if let Some(modified) = file.modified {
+                let time = UNIX_EPOCH + Duration::from_secs(modified.max(0) as u64);
+                output.set_modified(time).ok();
+            }
+        }
+        Ok(())
+    }
+
+    /// 展開したファイルを読み直して記録のチェックサムと照合する
+    fn verify_extracted(manifest: &ArchiveManifest, dir: &Path) -> Result<()> {
+        let mut mismatched = Vec::new();
+        for file in &manifest.files {
+            let path = dir.join(safe_relative(&file.path)?);
+            let matches = File::open(&path)
+                .map(BufReader::new)
+                .and_then(|mut reader| hash_copy(&mut reader, &mut io::sink()))
+                .map(|(size, sha256)| size == file.size && sha256 == file.sha256)
+                .unwrap_or(false);
+            if !matches {
+                mismatched.push(file.path.clone());
+            }
+        }
+        if !mismatched.is_empty() {
+            bail!("展開後のチェックサムが一致しません: {}", mismatched.join(", "));
+        }
+        Ok(())
+    }
+
+    /// アーカイブを開いて記録を読む
+    pub fn open(zip_path: &Path) -> Result<(ArchiveManifest, ZipArchive<BufReader<File>>)> {
+        let file = File::open(zip_path)
+            .with_context(|| format!("アーカイブを開けません: {}", zip_path.display()))?;
+        let mut archive = ZipArchive::new(BufReader::new(file))
+            .with_context(|| format!("zip として読み込めません: {}", zip_path.display()))?;
+
+        let mut content = String::new();
+        archive
+            .by_name(MANIFEST_NAME)
+            .with_context(|| format!("アーカイブの記録がありません: {}", zip_path.display()))?
+            .read_to_string(&mut content)?;
+        let manifest = serde_json::from_str(&content)
+            .with_context(|| format!("アーカイブの記録の解析に失敗: {}", zip_path.display()))?;
+        Ok((manifest, archive))
+    }
+
+    /// 1_projects/<category>/<project> の一覧
+    fn list_projects(root: &Path) -> Vec<(String, PathBuf)> {
+        let projects_dir = root.join("1_projects");
+        let mut projects = Vec::new();
+        for category in ProjectScaffold::categories(root) {
+            let Ok(entries) = fs::read_dir(projects_dir.join(&category)) else {
+                continue;
+            };
+            let mut dirs: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir() && !file_name(path).starts_with('.'))
+                .collect();
+            dirs.sort();
+            projects.extend(dirs.into_iter().map(|dir| (category.clone(), dir)));
+        }
+        projects
+    }
+
+    /// `category/YYYYMMDD_name` またはフォルダ名でプロジェクトを探す
+    fn find_project(all: &[(String, PathBuf)], name: &str) -> Result<(String, PathBuf)> {
+        let name = name.trim_end_matches(['/', '\\']);
+        let matches: Vec<&(String, PathBuf)> = all
+            .iter()
+            .filter(|(category, dir)| {
+                let folder = file_name(dir);
+                name == folder || name.replace('\\', "/") == format!("{}/{}", category, folder)
+            })
+            .collect();
+        match matches.as_slice() {
+            [found] => Ok((*found).clone()),
+            [] => Err(anyhow!("プロジェクトが見つかりません: {}", name)),
+            _ => Err(anyhow!(
+                "同じ名前のプロジェクトが複数あります（category/name で指定してください）: {}",
+                name
+            )),
+        }
+    }
+
+    fn compression_for(path: &Path) -> CompressionMethod {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        if STORED_EXTENSIONS.contains(&extension.as_str()) {
+            CompressionMethod::Stored
+        } else {
+            CompressionMethod::Deflated
+        }
+    }
+}
+
+/// 読みながら SHA-256 を計算して書き出す（バイト数と 16 進のハッシュを返す）
+pub fn hash_copy<R: Read, W: Write>(reader: &mut R, writer: &mut W) -> io::Result<(u64, String)> {
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut total = 0u64;
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        writer.write_all(&buffer[..read])?;
+        total += read as u64;
+    }
+    let hash = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    Ok((total, hash))
+}
+
+/// 記録の `/` 区切りのパスを、外に出ない相対パスとして読む
+fn safe_relative(path: &str) -> Result<PathBuf> {
+    let relative: PathBuf = path.split('/').filter(|part| !part.is_empty()).collect();
+    let inside = relative.components().count() > 0
+        && relative
+            .components()
+            .all(|component| matches!(component, std::path::Component::Normal(_)));
+    if !inside {
+        bail!("アーカイブの記録に不正なパスがあります: {}", path);
+    }
+    Ok(relative)
+}
+
+fn unix_seconds(time: SystemTime) -> Option<i64> {
+    time.duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_secs() as i64)
+}
+
+/// zip に記録する更新日時（ローカル時刻、1980 年より前は記録しない）
+fn zip_time(time: SystemTime) -> Option<zip::DateTime> {
+    let local = chrono::DateTime::<chrono::Local>::from(time);
+    zip::DateTime::from_date_and_time(
+        u16::try_from(local.year()).ok()?,
+        local.month() as u8,
+        local.day() as u8,
+        local.hour() as u8,
+        local.minute() as u8,
+        local.second() as u8,
+    )
+    .ok()
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LookerConfig;
+    use crate::gallery_link::GalleryManifest;
+    use crate::gallery_manager::GalleryOptions;
+    use crate::test_support::TempDir;
+
+    /// `1_projects/film/20240301_promo` を作ってアーカイブし、zip のパスを返す
+    fn archive_fixture(root: &Path) -> PathBuf {
+        let project = root.join("1_projects/film/20240301_promo");
+        fs::create_dir_all(project.join("render")).unwrap();
+        fs::create_dir_all(project.join("empty")).unwrap();
+        fs::write(project.join("render/20240301_promo.mp4"), b"movie").unwrap();
+        fs::write(project.join("notes.txt"), "メモ").unwrap();
+
+        let plan = ProjectArchive::plan(root, &["film/20240301_promo".to_string()], None).unwrap();
+        ProjectArchive::apply(root, &plan).unwrap();
+        plan.items[0].archive_path.clone()
+    }
+
+    #[test]
+    fn archive_verifies_checksums_and_removes_the_project() {
+        let root = TempDir::new("archive-apply");
+        let project = root.join("1_projects/film/20240301_promo");
+        fs::create_dir_all(project.join("render")).unwrap();
+        fs::write(project.join("20240301_promo.mp4"), b"movie").unwrap();
+        fs::write(project.join("notes.txt"), "メモ").unwrap();
+        // ギャラリーのエントリを作っておく
+        let options = GalleryOptions::from_config(&LookerConfig::default());
+        let gallery = GalleryManager::plan(&root, &options).unwrap();
+        GalleryManager::apply(&root, &gallery, &options).unwrap();
+        let links: Vec<String> = GalleryManifest::load(&root)
+            .unwrap()
+            .entries
+            .into_values()
+            .map(|entry| entry.link)
+            .collect();
+        assert_eq!(links.len(), 1);
+        assert!(root.join("5_gallery").join(&links[0]).symlink_metadata().is_ok());
+
+        let plan = ProjectArchive::plan(&root, &["20240301_promo".to_string()], None).unwrap();
+        ProjectArchive::apply(&root, &plan).unwrap();
+
+        // 記録のすべてのファイルが zip の中身と一致する
+        let (manifest, mut archive) = ProjectArchive::open(&plan.items[0].archive_path).unwrap();
+        let mut paths: Vec<&str> = manifest.files.iter().map(|file| file.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["20240301_promo.mp4", "notes.txt"]);
+        for file in &manifest.files {
+            let mut entry = archive.by_name(&format!("20240301_promo/{}", file.path)).unwrap();
+            let (size, sha256) = hash_copy(&mut entry, &mut io::sink()).unwrap();
+            assert_eq!((size, sha256.as_str()), (file.size, file.sha256.as_str()), "{}", file.path);
+        }
+        let (_, movie_sha256) = hash_copy(&mut &b"movie"[..], &mut io::sink()).unwrap();
+        assert!(manifest.files.iter().any(|file| file.sha256 == movie_sha256));
+
+        // 元のフォルダとギャラリーのエントリは消える
+        assert!(!project.exists());
+        assert!(root.join("5_gallery").join(&links[0]).symlink_metadata().is_err());
+        assert!(GalleryManifest::load(&root).unwrap().entries.is_empty());
+    }
+
+    /// zip の記録の SHA-256 を書き換えた複製を作る
+    fn tamper(zip_path: &Path) -> PathBuf {
+        let (mut manifest, mut archive) = ProjectArchive::open(zip_path).unwrap();
+        let tampered = zip_path.with_file_name("tampered.zip");
+        let mut zip = ZipWriter::new(File::create(&tampered).unwrap());
+        for index in 0..archive.len() {
+            let entry = archive.by_index_raw(index).unwrap();
+            if entry.name() != MANIFEST_NAME {
+                zip.raw_copy_file(entry).unwrap();
+            }
+        }
+        manifest.files[0].sha256 = "0".repeat(64);
+        zip.start_file(MANIFEST_NAME, SimpleFileOptions::default()).unwrap();
+        zip.write_all(serde_json::to_string(&manifest).unwrap().as_bytes()).unwrap();
+        zip.finish().unwrap();
+        tampered
+    }
+
+    #[test]
+    fn restore_into_a_fresh_location() {
+        let root = TempDir::new("restore-fresh");
+        let zip_path = archive_fixture(&root);
+        let project = root.join("1_projects/film/20240301_promo");
+        assert!(!project.exists());
+
+        let restored = ProjectArchive::restore(&root, &zip_path, false).unwrap();
+        assert_eq!(restored, project);
+        assert_eq!(fs::read(project.join("render/20240301_promo.mp4")).unwrap(), b"movie");
+        assert_eq!(fs::read_to_string(project.join("notes.txt")).unwrap(), "メモ");
+        assert!(project.join("empty").is_dir());
+        assert!(!root.join("1_projects/film/20240301_promo.restoring").exists());
+    }
+
+    #[test]
+    fn restore_refuses_an_existing_folder_without_force() {
+        let root = TempDir::new("restore-existing");
+        let zip_path = archive_fixture(&root);
+        let project = root.join("1_projects/film/20240301_promo");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join("new.txt"), "作業中").unwrap();
+
+        let error = ProjectArchive::restore(&root, &zip_path, false).unwrap_err();
+        assert!(error.to_string().contains("--force"));
+        assert_eq!(fs::read_to_string(project.join("new.txt")).unwrap(), "作業中");
+        assert!(!project.join("notes.txt").exists());
+
+        // --force なら中身ごと入れ替える
+        ProjectArchive::restore(&root, &zip_path, true).unwrap();
+        assert!(!project.join("new.txt").exists());
+        assert_eq!(fs::read_to_string(project.join("notes.txt")).unwrap(), "メモ");
+        assert!(!root.join("1_projects/film/20240301_promo.replaced").exists());
+    }
+
+    #[test]
+    fn restore_rejects_a_tampered_checksum() {
+        let root = TempDir::new("restore-tampered");
+        let zip_path = archive_fixture(&root);
+        let tampered = tamper(&zip_path);
+        let project = root.join("1_projects/film/20240301_promo");
+
+        let error = ProjectArchive::restore(&root, &tampered, false).unwrap_err();
+        assert!(format!("{error:#}").contains("チェックサムが一致しません"));
+        assert!(!project.exists());
+        assert!(!root.join("1_projects/film/20240301_promo.restoring").exists());
+
+        // --force でも失敗したら元のフォルダはそのまま
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join("new.txt"), "作業中").unwrap();
+        assert!(ProjectArchive::restore(&root, &tampered, true).is_err());
+        assert_eq!(fs::read_to_string(project.join("new.txt")).unwrap(), "作業中");
+        assert!(!project.join("notes.txt").exists());
+        assert!(!root.join("1_projects/film/20240301_promo.restoring").exists());
+    }
+
+    #[test]
+    fn huge_older_than_is_an_error() {
+        let root = TempDir::new("archive-older-than");
+        fs::create_dir_all(root.join("1_projects/film/20240301_promo")).unwrap();
+
+        let error = ProjectArchive::plan(&root, &[], Some(u64::MAX)).unwrap_err();
+        assert!(error.to_string().contains("経過日数が大きすぎます"));
+        assert!(ProjectArchive::plan(&root, &[], Some(u64::MAX / 86_400)).is_err());
+        assert!(ProjectArchive::plan(&root, &[], Some(30)).is_ok());
+    }
+}
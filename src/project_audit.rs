@@ -0,0 +1,414 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{anyhow, Context, Result};
+use walkdir::WalkDir;
+
+use crate::folder_note::FolderNotes;
+use crate::gallery_manager::{MediaKind, Revision};
+use crate::naming::NamingRule;
+use crate::project_meta::ProjectMeta;
+use crate::ui::UI;
+
+/// 1_projects の構成と命名のチェック
+pub struct ProjectAudit;
+
+/// チェックで見つかった問題の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectIssueKind {
+    /// カテゴリフォルダに入っていないプロジェクト
+    MissingCategory,
+    /// 1_projects 直下やカテゴリ直下に置かれたファイル
+    LooseFile,
+    /// YYYYMMDD_name に沿わないプロジェクトフォルダ名
+    InvalidName,
+    /// 成果物が無いプロジェクト
+    NoOutput,
+    /// フォルダ名と一致しない成果物名
+    OutputMismatch,
+}
+
+impl ProjectIssueKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProjectIssueKind::MissingCategory => "カテゴリ外",
+            ProjectIssueKind::LooseFile => "フォルダ外のファイル",
+            ProjectIssueKind::InvalidName => "命名規則違反",
+            ProjectIssueKind::NoOutput => "成果物なし",
+            ProjectIssueKind::OutputMismatch => "成果物名の不一致",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProjectIssue {
+    pub path: PathBuf,
+    pub kind: ProjectIssueKind,
+    pub detail: String,
+}
+
+/// 命名規則に合わせるための名前変更・移動
+#[derive(Debug, Clone)]
+pub struct ProjectRename {
+    pub source: PathBuf,
+    pub target: PathBuf,
+}
+
+#[derive(Debug)]
+pub struct ProjectAuditReport {
+    pub projects_dir: PathBuf,
+    pub categories: usize,
+    pub projects: usize,
+    pub issues: Vec<ProjectIssue>,
+    pub renames: Vec<ProjectRename>,
+}
+
+impl ProjectAuditReport {
+    fn issue(&mut self, path: &Path, kind: ProjectIssueKind, detail: impl Into<String>) {
+        self.issues.push(ProjectIssue {
+            path: path.to_path_buf(),
+            kind,
+            detail: detail.into(),
+        });
+    }
+
+    /// 変更先が既存のファイルや他の変更と重ならない場合だけ計画に加える
+    fn propose(&mut self, source: &Path, target: PathBuf) -> bool {
+        let taken = self.renames.iter().any(|rename| rename.target == target);
+        if taken || target.exists() {
+            return false;
+        }
+        self.renames.push(ProjectRename {
+            source: source.to_path_buf(),
+            target,
+        });
+        true
+    }
+}
+
+impl ProjectAudit {
+    /// 1_projects/[category]/[project] の構成と命名をチェックする（ファイルは変更しない）
+    ///
+    /// `match_threshold` はギャラリーと同じ、成果物名とフォルダ名の一致度のしきい値。
+    pub fn plan(root: &Path, match_threshold: f64) -> Result<ProjectAuditReport> {
+        let projects_dir = root.join("1_projects");
+        if !projects_dir.exists() {
+            return Err(anyhow!("1_projects フォルダが見つかりません: {}", projects_dir.display()));
+        }
+
+        let mut report = ProjectAuditReport {
+            projects_dir: projects_dir.clone(),
+            categories: 0,
+            projects: 0,
+            issues: Vec::new(),
+            renames: Vec::new(),
+        };
+
+        for entry in Self::read_entries(&projects_dir)? {
+            let name = Self::file_name(&entry);
+            if entry.is_dir() {
+                // 命名規則に沿わなくても日付付きのフォルダはプロジェクトとして扱う
+                if NamingRule::project_folder_name(&name).is_some() {
+                    report.issue(
+                        &entry,
+                        ProjectIssueKind::MissingCategory,
+                        "カテゴリフォルダ（1_projects/[category]/）の中に移動してください",
+                    );
+                    continue;
+                }
+                report.categories += 1;
+                Self::audit_category(&entry, match_threshold, &mut report)?;
+            } else {
+                report.issue(
+                    &entry,
+                    ProjectIssueKind::LooseFile,
+                    "1_projects 直下のファイルです",
+                );
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// 計画した名前変更を適用する（既存のファイルがある場合はスキップ）
+    pub fn apply(report: &ProjectAuditReport) -> Result<()> {
+        // フォルダ内のファイルを先に処理してから、フォルダ自体の名前を変える
+        let mut renames: Vec<&ProjectRename> = report.renames.iter().collect();
+        renames.sort_by_key(|rename| std::cmp::Reverse(rename.source.components().count()));
+
+        let mut renamed = 0usize;
+        let mut skipped = 0usize;
+        for rename in renames {
+            if rename.target.exists() {
+                UI::warning(&format!(
+                    "既存のファイルがあるためスキップ: {} -> {}",
+                    rename.source.display(),
+                    rename.target.display()
+                ));
+                skipped += 1;
+                continue;
+            }
+            if let Some(parent) = rename.target.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("フォルダの作成に失敗: {}", parent.display()))?;
+            }
+            fs::rename(&rename.source, &rename.target).with_context(|| {
+                format!(
+                    "名前の変更に失敗: {} -> {}",
+                    rename.source.display(),
+                    rename.target.display()
+                )
+            })?;
+            renamed += 1;
+        }
+
+        UI::success(&format!("名前を変更しました: {} 件（スキップ {} 件）", renamed, skipped));
+        if renamed > 0 {
+            UI::info("5_gallery のエントリを更新するには、ショートカットを作成し直してください。");
+        }
+        Ok(())
+    }
+
+    fn audit_category(
+        category_dir: &Path,
+        match_threshold: f64,
+        report: &mut ProjectAuditReport,
+    ) -> Result<()> {
+        for entry in Self::read_entries(category_dir)? {
+            if entry.is_dir() {
+                report.projects += 1;
+                Self::audit_project(&entry, match_threshold, report)?;
+                continue;
+            }
+
+            // YYYYMMDD_name.ext の成果物なら同名のプロジェクトフォルダに入れる
+            let stem = entry
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let proposed = NamingRule::split_dated_name(&stem).and_then(|(date, project)| {
+                let (base, _) = Revision::split(&project);
+                NamingRule::check_project_name(&base)
+                    .ok()
+                    .map(|_| category_dir.join(format!("{}_{}", date, base)))
+            });
+            let moved = match proposed {
+                Some(project_dir) => {
+                    let target = project_dir.join(Self::file_name(&entry));
+                    report.propose(&entry, target)
+                }
+                None => false,
+            };
+            let detail = if moved {
+                "カテゴリ直下のファイルです（同名のプロジェクトフォルダに移動します）"
+            } else {
+                "カテゴリ直下のファイルです"
+            };
+            report.issue(&entry, ProjectIssueKind::LooseFile, detail);
+        }
+        Ok(())
+    }
+
+    fn audit_project(
+        project_dir: &Path,
+        match_threshold: f64,
+        report: &mut ProjectAuditReport,
+    ) -> Result<()> {
+        let name = Self::file_name(project_dir);
+        let files = Self::scan_files(project_dir);
+
+        // フォルダ名のチェック（成果物名の照合には修正後の名前を使う）
+        let project_name = match NamingRule::check_project_folder_name(&name) {
+            Ok((_, project)) => project,
+            Err(err) => {
+                let fallback_date = Self::oldest_modified(project_dir, &files);
+                let suggestion = NamingRule::suggest_project_folder_name(&name, &fallback_date);
+                let detail = match &suggestion {
+                    Some(suggestion) => {
+                        let target = project_dir.with_file_name(suggestion);
+                        if report.propose(project_dir, target) {
+                            format!("{}（→ {}）", err, suggestion)
+                        } else {
+                            format!("{}（{} は既に存在します）", err, suggestion)
+                        }
+                    }
+                    None => err.to_string(),
+                };
+                report.issue(project_dir, ProjectIssueKind::InvalidName, detail);
+
+                suggestion
+                    .as_deref()
+                    .and_then(NamingRule::split_dated_name)
+                    .map(|(_, project)| project)
+                    .unwrap_or(name.clone())
+            }
+        };
+
+        // project.toml がある場合はその設定に従う
+        let meta = ProjectMeta::load(project_dir)?.unwrap_or_default();
+        if meta.exclude {
+            return Ok(());
+        }
+        if !meta.showcase.is_empty() {
+            let missing: Vec<String> = meta
+                .showcase
+                .iter()
+                .filter(|path| !project_dir.join(path).is_file())
+                .map(|path| path.display().to_string())
+                .collect();
+            if missing.len() == meta.showcase.len() {
+                report.issue(
+                    project_dir,
+                    ProjectIssueKind::NoOutput,
+                    format!("showcase のファイルが見つかりません: {}", missing.join(", ")),
+                );
+            }
+            return Ok(());
+        }
+
+        let mut has_output = false;
+        let mut mismatched = Vec::new();
+        for file in &files {
+            let extension = file
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            if MediaKind::from_extension(&extension).is_none() {
+                continue;
+            }
+            let stem = file
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            // 日付の無いファイルは素材として扱う
+            let Some((date, file_project)) = NamingRule::split_dated_name(&stem) else {
+                continue;
+            };
+
+            let (base, _) = Revision::split(&file_project);
+            let score = NamingRule::name_similarity(&base, &project_name);
+            if score >= match_threshold {
+                has_output = true;
+            } else {
+                let revision_suffix = file_project[base.len()..].to_string();
+                mismatched.push((file, date, revision_suffix, score));
+            }
+        }
+
+        if !has_output && mismatched.is_empty() {
+            report.issue(
+                project_dir,
+                ProjectIssueKind::NoOutput,
+                "YYYYMMDD_name.ext 形式の成果物がありません",
+            );
+        }
+
+        for (file, date, revision_suffix, score) in mismatched {
+            // 一致する成果物が他に無いときだけ、日付と版表記（_v2 / _final）を残して名前を揃える
+            let proposed = !has_output && {
+                let target = file.with_file_name(format!(
+                    "{}_{}{}.{}",
+                    date,
+                    project_name,
+                    revision_suffix,
+                    file.extension().unwrap_or_default().to_string_lossy()
+                ));
+                report.propose(file, target)
+            };
+            let detail = if proposed {
+                let target = &report.renames[report.renames.len() - 1].target;
+                format!("フォルダ名との一致度 {:.2}（→ {}）", score, Self::file_name(target))
+            } else {
+                format!("フォルダ名との一致度 {:.2}", score)
+            };
+            report.issue(file, ProjectIssueKind::OutputMismatch, detail);
+        }
+        Ok(())
+    }
+
+    /// フォルダ直下のエントリ（隠しファイルとプロジェクト設定を除く、名前順）
+    fn read_entries(dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(dir)
+            .with_context(|| format!("フォルダの読み込みに失敗: {}", dir.display()))?
+        {
+            let path = entry?.path();
+            if Self::is_hidden(&path) || ProjectMeta::is_meta_file(&path) {
+                continue;
+            }
+            entries.push(path);
+        }
+        entries.sort();
+        Ok(entries)
+    }
+
+    /// プロジェクト以下のファイル（隠しファイルを除く）
+    fn scan_files(project_dir: &Path) -> Vec<PathBuf> {
+        WalkDir::new(project_dir)
+            .follow_links(false)
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !Self::is_hidden(entry.path()))
+            .filter_map(|e| e.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .collect()
+    }
+
+    /// プロジェクト内で最も古い更新日（YYYYMMDD）。日付の無いフォルダ名の補完に使う
+    fn oldest_modified(project_dir: &Path, files: &[PathBuf]) -> String {
+        let oldest = files
+            .iter()
+            .chain(std::iter::once(&project_dir.to_path_buf()))
+            .filter_map(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+            .min()
+            .unwrap_or_else(SystemTime::now);
+        chrono::DateTime::<chrono::Local>::from(oldest)
+            .format("%Y%m%d")
+            .to_string()
+    }
+
+    fn is_hidden(path: &Path) -> bool {
+        path.file_name()
+            .map(|name| {
+                let name = name.to_string_lossy();
+                name.starts_with('.') || FolderNotes::is_note_file(&name)
+            })
+            .unwrap_or(false)
+    }
+
+    fn file_name(path: &Path) -> String {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn dated_folders_under_projects_are_missing_a_category() {
+        let root = TempDir::new("project-audit-category");
+        let projects = root.join("1_projects");
+        for dir in ["20240301_promo", "20240301_My Project", "film/20240302_trailer"] {
+            fs::create_dir_all(projects.join(dir)).unwrap();
+        }
+
+        let report = ProjectAudit::plan(&root, 0.8).unwrap();
+        let mut missing: Vec<String> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.kind == ProjectIssueKind::MissingCategory)
+            .map(|issue| ProjectAudit::file_name(&issue.path))
+            .collect();
+        missing.sort();
+        assert_eq!(missing, ["20240301_My Project", "20240301_promo"]);
+        assert_eq!(report.categories, 1);
+        assert_eq!(report.projects, 1);
+    }
+}
@@ -0,0 +1,56 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// プロジェクトフォルダに置くメタデータ（`project.toml` または `.looker.toml`）
+///
+/// ```toml
+/// title = "春のプロモーション"
+/// tags = ["client", "motion"]
+/// showcase = ["render/promo_final.mp4"]
+/// exclude = false
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectMeta {
+    /// ギャラリーに表示する名前
+    pub title: Option<String>,
+    /// 一覧ページなどに表示するタグ
+    pub tags: Vec<String>,
+    /// ギャラリーに載せるファイル（プロジェクトフォルダからの相対パス）
+    ///
+    /// 指定した場合は命名規則による探索を行わず、このファイルだけを載せる。
+    pub showcase: Vec<PathBuf>,
+    /// true ならこのプロジェクトをギャラリーに載せない
+    pub exclude: bool,
+}
+
+impl ProjectMeta {
+    /// 優先順に並べたメタデータのファイル名
+    pub const FILE_NAMES: [&'static str; 2] = ["project.toml", ".looker.toml"];
+
+    /// メタデータのファイル名かどうか
+    pub fn is_meta_file(path: &Path) -> bool {
+        path.file_name()
+            .map(|name| Self::FILE_NAMES.iter().any(|meta| name == *meta))
+            .unwrap_or(false)
+    }
+
+    /// プロジェクトフォルダのメタデータを読み込む（無ければ None）
+    pub fn load(project_dir: &Path) -> Result<Option<Self>> {
+        for file_name in Self::FILE_NAMES {
+            let path = project_dir.join(file_name);
+            if !path.is_file() {
+                continue;
+            }
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("プロジェクト設定の読み込みに失敗: {}", path.display()))?;
+            let meta = toml::from_str(&content)
+                .with_context(|| format!("プロジェクト設定の解析に失敗: {}", path.display()))?;
+            return Ok(Some(meta));
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,141 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use walkdir::WalkDir;
+
+use crate::config::ProjectConfig;
+use crate::naming::NamingRule;
+use crate::ui::UI;
+
+/// 新しいプロジェクトフォルダの作成
+pub struct ProjectScaffold;
+
+impl ProjectScaffold {
+    /// 1_projects 直下のカテゴリ名（隠しフォルダを除く、名前順）
+    pub fn categories(root: &Path) -> Vec<String> {
+        let mut categories: Vec<String> = fs::read_dir(root.join("1_projects"))
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.path().is_dir())
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .filter(|name| {
+                        !name.starts_with('.') && NamingRule::project_folder_name(name).is_none()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        categories.sort();
+        categories
+    }
+
+    /// 今日の日付で `1_projects/<category>/YYYYMMDD_<name>` を作成し、ひな形を展開する
+    ///
+    /// ギャラリーの探索と同じ判定でプロジェクトとして扱える名前だけを受け付ける。
+    /// 命名規則に沿わない文字（空白など）は警告だけ出す（audit で指摘される）。
+    pub fn create(root: &Path, category: &str, name: &str, config: &ProjectConfig) -> Result<PathBuf> {
+        Self::check_category(category)?;
+
+        if name.contains(['/', '\\']) {
+            bail!("プロジェクト名にフォルダの区切り文字は使えません: {}", name);
+        }
+        let date = chrono::Local::now().format("%Y%m%d").to_string();
+        let folder_name = format!("{}_{}", date, name);
+        if NamingRule::project_folder_name(&folder_name).is_none() {
+            bail!("プロジェクト名を入力してください");
+        }
+        if let Err(err) = NamingRule::check_project_name(name) {
+            let hint = NamingRule::sanitize_project_name(name)
+                .filter(|sanitized| sanitized != name)
+                .map(|sanitized| format!("（例: {}）", sanitized))
+                .unwrap_or_default();
+            UI::warning(&format!("プロジェクト名が命名規則に沿っていません: {}{}", err, hint));
+        }
+
+        let category_dir = root.join("1_projects").join(category);
+        let project_dir = category_dir.join(&folder_name);
+        if project_dir.exists() {
+            bail!("プロジェクトフォルダが既に存在します: {}", project_dir.display());
+        }
+        // ひな形の指定は作成前にすべて確認する（途中で失敗してフォルダが残らないように）
+        let folders = config
+            .folders
+            .iter()
+            .map(|folder| Self::template_path(folder))
+            .collect::<Result<Vec<_>>>()?;
+        let template_dir = config.template_dir.as_ref().map(|dir| root.join(dir));
+        if let Some(template_dir) = &template_dir
+            && !template_dir.is_dir()
+        {
+            bail!("ひな形フォルダが見つかりません: {}", template_dir.display());
+        }
+
+        if !category_dir.exists() {
+            UI::info(&format!("カテゴリを作成します: {}", category));
+        }
+        fs::create_dir_all(&project_dir)
+            .with_context(|| format!("フォルダの作成に失敗: {}", project_dir.display()))?;
+        for folder in folders {
+            let path = project_dir.join(folder);
+            fs::create_dir_all(&path)
+                .with_context(|| format!("フォルダの作成に失敗: {}", path.display()))?;
+        }
+        if let Some(template_dir) = &template_dir {
+            Self::copy_template(template_dir, &project_dir)?;
+        }
+
+        UI::success(&format!("プロジェクトを作成しました: {}", project_dir.display()));
+        Ok(project_dir)
+    }
+
+    /// カテゴリ名のチェック（1 階層のフォルダ名のみ）
+    fn check_category(category: &str) -> Result<()> {
+        if category.is_empty()
+            || category.starts_with('.')
+            || category.contains(['/', '\\'])
+        {
+            bail!("カテゴリ名が不正です: {:?}", category);
+        }
+        if NamingRule::project_folder_name(category).is_some() {
+            bail!("カテゴリ名に YYYYMMDD_name の形式は使えません: {}", category);
+        }
+        Ok(())
+    }
+
+    /// ひな形のフォルダ指定をプロジェクトフォルダ内の相対パスに変換する
+    fn template_path(folder: &str) -> Result<PathBuf> {
+        let path = PathBuf::from(folder);
+        let inside = path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+        if !inside {
+            bail!("ひな形のフォルダはプロジェクト内の相対パスで指定してください: {}", folder);
+        }
+        Ok(path)
+    }
+
+    /// ひな形フォルダの中身をコピーする（既存のファイルは上書きしない）
+    fn copy_template(template_dir: &Path, project_dir: &Path) -> Result<()> {
+        for entry in WalkDir::new(template_dir)
+            .follow_links(false)
+            .min_depth(1)
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            let Ok(relative) = entry.path().strip_prefix(template_dir) else {
+                continue;
+            };
+            let target = project_dir.join(relative);
+            if entry.file_type().is_dir() {
+                fs::create_dir_all(&target)
+                    .with_context(|| format!("フォルダの作成に失敗: {}", target.display()))?;
+            } else if entry.file_type().is_file() && !target.exists() {
+                fs::copy(entry.path(), &target).with_context(|| {
+                    format!("コピーに失敗: {:?} -> {:?}", entry.path(), target)
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -4,8 +4,12 @@ use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Context, Result};
 use chrono::{Datelike, Local};
+use rayon::prelude::*;
 
+use crate::folder_note::FolderNotes;
 use crate::naming::NamingRule;
+use crate::routing_rules::{Route, RoutingRules};
+use crate::scan_index::ScanIndex;
 use crate::scanner::{DriveScanner, FileInfo};
 
 /// Record フォルダを整理するメインロジック
@@ -40,18 +44,23 @@ impl RecordType {
 pub struct RecordOptions {
     pub target_types: Vec<RecordType>,
     pub check_misplaced: bool,
+    /// `.looker/rules.toml` の振り分けルール（一致したファイルは種別の推定より優先）
+    pub rules: RoutingRules,
+    /// 走査結果のキャッシュ（`.lookerignore` を読むルートもここから決まる）
+    pub index: ScanIndex,
 }
 
-impl Default for RecordOptions {
-    fn default() -> Self {
+impl RecordOptions {
+    /// 既定の設定（すべての種別・誤配置のチェックあり・ルールなし）
+    pub fn new(index: ScanIndex) -> Self {
         Self {
             target_types: Vec::new(),
             check_misplaced: true,
+            rules: RoutingRules::default(),
+            index,
         }
     }
-}
 
-impl RecordOptions {
     pub fn includes(&self, record_type: &RecordType) -> bool {
         self.target_types.is_empty()
             || self
@@ -75,6 +84,17 @@ pub enum ActionType {
     MoveToCorrectLocation,
 }
 
+/// ルールファイルによる振り分けの結果
+#[derive(Debug)]
+pub enum Routed {
+    /// どのルールにも一致しない（通常の整理に回す）
+    NoMatch,
+    /// 一致したが動かす必要がない（既に行き先にある・その場に残すルール）
+    AlreadyInPlace,
+    /// 一致して移動・名前の変更が必要
+    Action(RecordFileAction),
+}
+
 #[derive(Debug)]
 pub struct RecordOrganizationPlan {
     pub record_root: PathBuf,
@@ -95,6 +115,14 @@ impl RecordOrganizationPlan {
         self.actions.is_empty() && self.required_folders.is_empty()
     }
 
+    /// アクションを追加し、移動先のフォルダを登録する
+    pub fn push_action(&mut self, action: RecordFileAction) {
+        if let Some(parent) = action.target.parent() {
+            self.register_folder(parent);
+        }
+        self.actions.push(action);
+    }
+
     pub fn register_folder<P: AsRef<Path>>(&mut self, folder: P) {
         let folder = folder.as_ref();
         if !folder.exists() {
@@ -123,8 +151,17 @@ impl RecordManager {
         let mut planned_targets: BTreeSet<PathBuf> = BTreeSet::new();
 
         // 1. record_root 直下のファイルを整理
-        let root_files = Self::scan_record_folder(record_root)?;
+        let root_files = Self::scan_record_folder(record_root, &options.index)?;
         for file in root_files {
+            match Self::route_by_rules(&file, &options.rules, &mut planned_targets)? {
+                Routed::NoMatch => {}
+                Routed::AlreadyInPlace => continue,
+                Routed::Action(action) => {
+                    plan.push_action(action);
+                    continue;
+                }
+            }
+
             let record_type = Self::guess_record_type(&file.path);
             if !options.includes(&record_type) {
                 continue;
@@ -174,8 +211,17 @@ impl RecordManager {
                 continue;
             }
 
-            let files = Self::scan_record_folder(&record_path)?;
+            let files = Self::scan_record_folder(&record_path, &options.index)?;
             for file in files {
+                match Self::route_by_rules(&file, &options.rules, &mut planned_targets)? {
+                    Routed::NoMatch => {}
+                    Routed::AlreadyInPlace => continue,
+                    Routed::Action(action) => {
+                        plan.push_action(action);
+                        continue;
+                    }
+                }
+
                 let needs_rename = !NamingRule::check_record_naming(&file.name);
                 let target_folder =
                     Self::determine_target_folder(&file, &record_path, &record_type)?;
@@ -228,6 +274,15 @@ impl RecordManager {
     ///
     /// 可能な限り処理を継続し、失敗しても最後にサマリを表示します。
     pub fn apply(plan: &RecordOrganizationPlan) -> Result<()> {
+        Self::apply_actions(plan, true)
+    }
+
+    /// record 以外（downloads など）のプランを適用する（空フォルダの片付けは行わない）
+    pub fn apply_moves(plan: &RecordOrganizationPlan) -> Result<()> {
+        Self::apply_actions(plan, false)
+    }
+
+    fn apply_actions(plan: &RecordOrganizationPlan, cleanup_record_dirs: bool) -> Result<()> {
         use crate::ui::UI;
 
         // サマリ用カウンタ
@@ -299,20 +354,19 @@ impl RecordManager {
             UI::info(&format!("\nファイルを移動中... ({} 件)", actions_total));
         }
         for (idx, action) in effective_actions.iter().enumerate() {
-            if let Some(parent) = action.target.parent() {
-                if !parent.exists() {
-                    if let Err(e) = fs::create_dir_all(parent) {
-                        actions_failed_err += 1;
-                        UI::warning(&format!(
-                            "  [{}] 親フォルダ作成失敗: {} ({})",
-                            idx + 1,
-                            parent.display(),
-                            e
-                        ));
-                        // 親フォルダが作れないと移動できないので次へ
-                        continue;
-                    }
-                }
+            if let Some(parent) = action.target.parent()
+                && !parent.exists()
+                && let Err(e) = fs::create_dir_all(parent)
+            {
+                actions_failed_err += 1;
+                UI::warning(&format!(
+                    "  [{}] 親フォルダ作成失敗: {} ({})",
+                    idx + 1,
+                    parent.display(),
+                    e
+                ));
+                // 親フォルダが作れないと移動できないので次へ
+                continue;
             }
 
             match fs::rename(&action.source, &action.target) {
@@ -341,9 +395,11 @@ impl RecordManager {
         }
 
         // 4. 規定外サブフォルダで空になったものを片付ける（失敗しても続行）
-        UI::info("\n空フォルダをクリーンアップ中...");
-        if let Err(e) = Self::cleanup_non_standard_empty_dirs(&plan.record_root) {
-            UI::warning(&format!("空フォルダのクリーンアップに失敗: {}", e));
+        if cleanup_record_dirs {
+            UI::info("\n空フォルダをクリーンアップ中...");
+            if let Err(e) = Self::cleanup_non_standard_empty_dirs(&plan.record_root) {
+                UI::warning(&format!("空フォルダのクリーンアップに失敗: {}", e));
+            }
         }
 
         // 5. サマリ表示（必ず最後に出す）
@@ -371,20 +427,53 @@ impl RecordManager {
     ) -> Result<Vec<RecordFileAction>> {
         let mut actions = Vec::new();
 
-        // 1. 各 record 種別配下を再帰的にチェック
-        for record_type in Self::RECORD_TYPES {
-            if !options.includes(&record_type) {
-                continue;
-            }
-
-            let record_path = record_base.join(record_type.folder_name());
-            if !record_path.exists() {
-                continue;
-            }
-
-            let all_files = Self::scan_all_files_recursive(&record_path)?;
+        // 走査は並列に行い、移動先の重複を避けるためアクションの組み立ては決まった順に行う
+        let type_paths: Vec<(RecordType, PathBuf)> = Self::RECORD_TYPES
+            .into_iter()
+            .filter(|record_type| options.includes(record_type))
+            .map(|record_type| {
+                let record_path = record_base.join(record_type.folder_name());
+                (record_type, record_path)
+            })
+            .filter(|(_, record_path)| record_path.exists())
+            .collect();
+        // record_root 直下の「規定外サブフォルダ」（名前順）
+        let sub_roots: Vec<PathBuf> = options
+            .index
+            .list(record_base)
+            .with_context(|| format!("record ルートの走査に失敗: {:?}", record_base))?
+            .into_iter()
+            .filter(|entry| {
+                entry.is_dir
+                    && !Self::RECORD_TYPES
+                        .iter()
+                        .any(|kind| kind.folder_name() == entry.name)
+            })
+            .map(|entry| entry.path)
+            .collect();
+
+        let mut scanned: Vec<Vec<FileInfo>> = type_paths
+            .iter()
+            .map(|(_, record_path)| record_path)
+            .chain(&sub_roots)
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|path| Self::scan_all_files_recursive(path, &options.index))
+            .collect::<Result<_>>()?;
+        let sub_scanned = scanned.split_off(type_paths.len());
 
+        // 1. 各 record 種別配下を再帰的にチェック
+        for ((record_type, record_path), all_files) in type_paths.into_iter().zip(scanned) {
             for file in all_files {
+                match Self::route_by_rules(&file, &options.rules, planned_targets)? {
+                    Routed::NoMatch => {}
+                    Routed::AlreadyInPlace => continue,
+                    Routed::Action(action) => {
+                        actions.push(action);
+                        continue;
+                    }
+                }
+
                 let correct_type = Self::guess_record_type(&file.path);
                 if !options.includes(&correct_type) {
                     continue;
@@ -439,36 +528,17 @@ impl RecordManager {
         }
 
         // 2. record_root 直下の「規定外サブフォルダ」配下を整理
-        let entries = fs::read_dir(record_base)
-            .with_context(|| format!("record ルートの走査に失敗: {:?}", record_base))?;
-
-        for entry in entries {
-            let entry = match entry {
-                Ok(entry) => entry,
-                Err(_) => continue,
-            };
-
-            let metadata = match entry.metadata() {
-                Ok(metadata) => metadata,
-                Err(_) => continue,
-            };
-
-            if !metadata.is_dir() {
-                continue;
-            }
-
-            let name = entry.file_name().to_string_lossy().to_string();
-            let is_standard = Self::RECORD_TYPES
-                .iter()
-                .any(|kind| kind.folder_name() == name);
-            if is_standard {
-                continue;
-            }
-
-            let sub_root = entry.path();
-            let sub_files = Self::scan_all_files_recursive(&sub_root)?;
-
+        for sub_files in sub_scanned {
             for file in sub_files {
+                match Self::route_by_rules(&file, &options.rules, planned_targets)? {
+                    Routed::NoMatch => {}
+                    Routed::AlreadyInPlace => continue,
+                    Routed::Action(action) => {
+                        actions.push(action);
+                        continue;
+                    }
+                }
+
                 let correct_type = Self::guess_record_type(&file.path);
                 if !options.includes(&correct_type) {
                     continue;
@@ -513,56 +583,67 @@ impl RecordManager {
     }
 
     /// 再帰的にファイルのみ取得
-    fn scan_all_files_recursive(record_path: &Path) -> Result<Vec<FileInfo>> {
-        let all_files = DriveScanner::scan(record_path)?;
-        Ok(all_files.into_iter().filter(|info| !info.is_dir).collect())
+    fn scan_all_files_recursive(record_path: &Path, index: &ScanIndex) -> Result<Vec<FileInfo>> {
+        let all_files = DriveScanner::scan(record_path, index)?;
+        Ok(all_files
+            .into_iter()
+            .filter(|info| !info.is_dir && !FolderNotes::is_note_file(&info.name))
+            .collect())
     }
 
     /// 指定フォルダ直下のファイルのみ取得
-    fn scan_record_folder(record_path: &Path) -> Result<Vec<FileInfo>> {
-        let mut files = Vec::new();
-        let entries = fs::read_dir(record_path)
-            .with_context(|| format!("ディレクトリの読み取りに失敗: {:?}", record_path))?;
-
-        for entry in entries {
-            let entry = match entry {
-                Ok(entry) => entry,
-                Err(_) => continue,
-            };
-
-            let metadata = match entry.metadata() {
-                Ok(metadata) => metadata,
-                Err(_) => continue,
-            };
+    pub fn scan_record_folder(record_path: &Path, index: &ScanIndex) -> Result<Vec<FileInfo>> {
+        Ok(index
+            .list(record_path)?
+            .into_iter()
+            .filter(|info| !info.is_dir && !FolderNotes::is_note_file(&info.name))
+            .collect())
+    }
 
-            if metadata.is_dir() {
-                continue;
-            }
+    /// ルールファイルで振り分ける
+    ///
+    /// どのルールにも一致しなければ `NoMatch`、一致したルールが移動しないか
+    /// 既に行き先にあれば `AlreadyInPlace`、それ以外は移動・リネームの `Action` を返す。
+    pub fn route_by_rules(
+        file: &FileInfo,
+        rules: &RoutingRules,
+        planned_targets: &mut BTreeSet<PathBuf>,
+    ) -> Result<Routed> {
+        let Some(matched) = rules.route(file)? else {
+            return Ok(Routed::NoMatch);
+        };
+        let Route::Move { folder, file_name } = matched.route else {
+            return Ok(Routed::AlreadyInPlace);
+        };
 
-            let path = entry.path();
-            let name = entry.file_name().to_string_lossy().to_string();
-            let extension = path
-                .extension()
-                .and_then(|ext| ext.to_str())
-                .unwrap_or("")
-                .to_lowercase();
-
-            let modified = metadata
-                .modified()
-                .map(chrono::DateTime::<chrono::Local>::from)
-                .unwrap_or_else(|_| chrono::Local::now());
-
-            files.push(FileInfo {
-                path,
-                name,
-                extension,
-                size: metadata.len(),
-                modified,
-                is_dir: false,
-            });
+        // 行き先に同じ名前（重複回避の -N 付きを含む）で置かれていれば何もしない
+        if file.path.parent() == Some(folder.as_path())
+            && (file.name == file_name || Self::is_numbered_variant(&file.name, &file_name))
+        {
+            return Ok(Routed::AlreadyInPlace);
         }
 
-        Ok(files)
+        let target = Self::unique_target_path(&folder, &file_name, planned_targets)?;
+        Ok(Routed::Action(RecordFileAction {
+            source: file.path.clone(),
+            target,
+            action_type: if file.name == file_name {
+                ActionType::Move
+            } else {
+                ActionType::Rename
+            },
+        }))
+    }
+
+    /// `name` が `base` に重複回避の `-N` を付けた名前か
+    fn is_numbered_variant(name: &str, base: &str) -> bool {
+        let (stem, ext) = name.rsplit_once('.').unwrap_or((name, ""));
+        let (base_stem, base_ext) = base.rsplit_once('.').unwrap_or((base, ""));
+        ext == base_ext
+            && stem
+                .strip_prefix(base_stem)
+                .and_then(|rest| rest.strip_prefix('-'))
+                .is_some_and(|index| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()))
     }
 
     /// 拡張子やファイル名から record 種別を推定
@@ -637,10 +718,11 @@ impl RecordManager {
                 prefix_with_ext.to_string()
             };
 
-            if let Some((head, tail)) = prefix.rsplit_once('-') {
-                if !tail.is_empty() && tail.chars().all(|c| c.is_ascii_digit()) {
-                    prefix = head.to_string();
-                }
+            if let Some((head, tail)) = prefix.rsplit_once('-')
+                && !tail.is_empty()
+                && tail.chars().all(|c| c.is_ascii_digit())
+            {
+                prefix = head.to_string();
             }
 
             return prefix;
@@ -668,7 +750,7 @@ impl RecordManager {
     ///
     /// - ベース名で空いていればそのまま使う
     /// - 既に存在していれば `-2`, `-3`, ... のように番号を振って空き名を探す
-    fn unique_target_path(
+    pub fn unique_target_path(
         target_folder: &Path,
         base_filename: &str,
         planned_targets: &mut BTreeSet<PathBuf>,
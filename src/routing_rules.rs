@@ -0,0 +1,763 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{Datelike, Local, NaiveDate};
+use globset::{GlobBuilder, GlobMatcher};
+use regex::{Regex, RegexBuilder};
+use serde::Deserialize;
+
+use crate::scanner::FileInfo;
+use crate::ui::UI;
+
+/// `<root>/.looker/rules.toml` の振り分けルール
+///
+/// 上から順に評価し、最初に一致したルールで移動先と名前を決める。
+/// どのルールにも一致しないファイルは既定の振り分けに任せる。
+#[derive(Debug, Clone, Default)]
+pub struct RoutingRules {
+    root: PathBuf,
+    rules: Vec<RoutingRule>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RoutingRule {
+    pub name: String,
+    glob: Option<(String, GlobMatcher)>,
+    regex: Option<Regex>,
+    extensions: Vec<String>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    modified_after: Option<NaiveDate>,
+    modified_before: Option<NaiveDate>,
+    older_than_days: Option<u64>,
+    newer_than_days: Option<u64>,
+    source: Option<(String, GlobMatcher)>,
+    target: Option<Template>,
+    rename: Option<Template>,
+}
+
+/// 一致したルールの行き先
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    /// その場に残す（`target` も `rename` も無いルール）
+    Keep,
+    /// `folder/file_name` に移動する
+    Move { folder: PathBuf, file_name: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct RuleMatch {
+    pub rule: String,
+    pub route: Route,
+}
+
+/// `rules test` 用の評価結果（一致したルールまで）
+#[derive(Debug, Clone)]
+pub struct RuleTrace {
+    pub rule: String,
+    /// 一致しなかった理由（一致した場合は None）
+    pub mismatch: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RulesFile {
+    rule: Vec<RuleSpec>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+struct RuleSpec {
+    name: Option<String>,
+    glob: Option<String>,
+    regex: Option<String>,
+    extension: Vec<String>,
+    min_size: Option<SizeSpec>,
+    max_size: Option<SizeSpec>,
+    modified_after: Option<String>,
+    modified_before: Option<String>,
+    older_than_days: Option<u64>,
+    newer_than_days: Option<u64>,
+    source: Option<String>,
+    target: Option<String>,
+    rename: Option<String>,
+}
+
+/// バイト数（`1048576`）または単位付きの文字列（`"50MB"`）
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SizeSpec {
+    Bytes(u64),
+    Text(String),
+}
+
+impl SizeSpec {
+    fn bytes(&self) -> Result<u64> {
+        match self {
+            SizeSpec::Bytes(bytes) => Ok(*bytes),
+            SizeSpec::Text(text) => parse_size(text),
+        }
+    }
+}
+
+/// `50MB` や `1.5GB` のようなサイズの指定を読む（単位を省略するとバイト）
+pub fn parse_size(text: &str) -> Result<u64> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| anyhow!("サイズの指定が不正です: {:?}", text))?;
+    let scale: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" => 1 << 10,
+        "M" | "MB" => 1 << 20,
+        "G" | "GB" => 1 << 30,
+        "T" | "TB" => 1 << 40,
+        _ => bail!("サイズの単位が不正です（B / KB / MB / GB / TB）: {:?}", text),
+    };
+    Ok((number * scale as f64) as u64)
+}
+
+/// `{var}` を含む移動先・命名のテンプレート
+#[derive(Debug, Clone)]
+struct Template {
+    source: String,
+    parts: Vec<TemplatePart>,
+}
+
+#[derive(Debug, Clone)]
+enum TemplatePart {
+    Text(String),
+    Var(TemplateVar),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum TemplateVar {
+    Name,
+    Stem,
+    Ext,
+    Year,
+    Month,
+    Day,
+    YearMonth,
+    Date,
+    Timestamp,
+    Period,
+}
+
+impl TemplateVar {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "name" => TemplateVar::Name,
+            "stem" => TemplateVar::Stem,
+            "ext" => TemplateVar::Ext,
+            "yyyy" => TemplateVar::Year,
+            "mm" => TemplateVar::Month,
+            "dd" => TemplateVar::Day,
+            "yyyymm" => TemplateVar::YearMonth,
+            "yyyymmdd" => TemplateVar::Date,
+            "timestamp" => TemplateVar::Timestamp,
+            "period" => TemplateVar::Period,
+            _ => return None,
+        })
+    }
+}
+
+impl Template {
+    fn parse(source: &str) -> Result<Self> {
+        let mut parts = Vec::new();
+        let mut rest = source;
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                parts.push(TemplatePart::Text(rest[..start].to_string()));
+            }
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| anyhow!("テンプレートの {{ が閉じていません: {}", source))?;
+            let name = &rest[start + 1..start + end];
+            let var = TemplateVar::parse(name)
+                .ok_or_else(|| anyhow!("テンプレートの変数が不明です: {{{}}}（{}）", name, source))?;
+            parts.push(TemplatePart::Var(var));
+            rest = &rest[start + end + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(TemplatePart::Text(rest.to_string()));
+        }
+        Ok(Self {
+            source: source.to_string(),
+            parts,
+        })
+    }
+
+    /// ファイルの名前と更新日時から展開する
+    fn render(&self, file: &FileInfo) -> String {
+        let stem = Path::new(&file.name)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| file.name.clone());
+        let date = file.modified;
+
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                TemplatePart::Text(text) => out.push_str(text),
+                TemplatePart::Var(var) => out.push_str(&match var {
+                    TemplateVar::Name => file.name.clone(),
+                    TemplateVar::Stem => stem.clone(),
+                    TemplateVar::Ext => file.extension.clone(),
+                    TemplateVar::Year => date.format("%Y").to_string(),
+                    TemplateVar::Month => date.format("%m").to_string(),
+                    TemplateVar::Day => date.format("%d").to_string(),
+                    TemplateVar::YearMonth => date.format("%Y%m").to_string(),
+                    TemplateVar::Date => date.format("%Y%m%d").to_string(),
+                    TemplateVar::Timestamp => date.format("%Y%m%d%H%M%S").to_string(),
+                    // record の整理と同じく、昨年以前は YYYY/YYYYMM、今年は YYYYMM
+                    TemplateVar::Period => {
+                        if date.year() < Local::now().year() {
+                            date.format("%Y/%Y%m").to_string()
+                        } else {
+                            date.format("%Y%m").to_string()
+                        }
+                    }
+                }),
+            }
+        }
+        out
+    }
+}
+
+impl RoutingRules {
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(".looker").join("rules.toml")
+    }
+
+    /// ルールファイルを読み込む（無い場合はルール無し）
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = Self::path(root);
+        if !path.exists() {
+            return Ok(Self {
+                root: root.to_path_buf(),
+                rules: Vec::new(),
+            });
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("ルールファイルの読み込みに失敗: {}", path.display()))?;
+        let file: RulesFile = toml::from_str(&content)
+            .with_context(|| format!("ルールファイルの解析に失敗: {}", path.display()))?;
+
+        let rules = file
+            .rule
+            .into_iter()
+            .enumerate()
+            .map(|(index, spec)| {
+                let name = spec
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| format!("#{}", index + 1));
+                RoutingRule::compile(name.clone(), spec)
+                    .with_context(|| format!("ルール {} が不正です: {}", name, path.display()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            rules,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// 最初に一致したルールの行き先
+    pub fn route(&self, file: &FileInfo) -> Result<Option<RuleMatch>> {
+        for rule in &self.rules {
+            if rule.mismatch(&self.root, file).is_none() {
+                return Ok(Some(RuleMatch {
+                    rule: rule.name.clone(),
+                    route: rule.route(&self.root, file)?,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 一致するまでの各ルールの評価結果
+    pub fn explain(&self, file: &FileInfo) -> Vec<RuleTrace> {
+        let mut traces = Vec::new();
+        for rule in &self.rules {
+            let mismatch = rule.mismatch(&self.root, file);
+            let matched = mismatch.is_none();
+            traces.push(RuleTrace {
+                rule: rule.name.clone(),
+                mismatch,
+            });
+            if matched {
+                break;
+            }
+        }
+        traces
+    }
+
+    /// `rules test` の対象ファイルを読み込む
+    pub fn file_info(path: &Path) -> Result<FileInfo> {
+        let path = std::path::absolute(path)
+            .with_context(|| format!("パスの解決に失敗: {}", path.display()))?;
+        let metadata = fs::metadata(&path)
+            .with_context(|| format!("ファイルが見つかりません: {}", path.display()))?;
+        if metadata.is_dir() {
+            bail!("フォルダは指定できません: {}", path.display());
+        }
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        let modified = metadata
+            .modified()
+            .map(chrono::DateTime::<Local>::from)
+            .unwrap_or_else(|_| Local::now());
+        Ok(FileInfo {
+            path,
+            name,
+            extension,
+            size: metadata.len(),
+            modified,
+            is_dir: false,
+        })
+    }
+
+    /// ルートからの相対パス（`/` 区切り）
+    fn relative(root: &Path, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(root).ok()?;
+        Some(
+            relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+        )
+    }
+}
+
+impl RoutingRule {
+    fn compile(name: String, spec: RuleSpec) -> Result<Self> {
+        let glob = spec
+            .glob
+            .map(|pattern| {
+                let matcher = GlobBuilder::new(&pattern)
+                    .case_insensitive(true)
+                    .literal_separator(true)
+                    .build()
+                    .with_context(|| format!("glob が不正です: {}", pattern))?
+                    .compile_matcher();
+                Ok::<_, anyhow::Error>((pattern, matcher))
+            })
+            .transpose()?;
+        let source = spec
+            .source
+            .map(|pattern| {
+                let pattern = pattern.trim_matches('/').to_string();
+                let matcher = GlobBuilder::new(&pattern)
+                    .case_insensitive(true)
+                    .literal_separator(true)
+                    .build()
+                    .with_context(|| format!("source が不正です: {}", pattern))?
+                    .compile_matcher();
+                Ok::<_, anyhow::Error>((pattern, matcher))
+            })
+            .transpose()?;
+        let regex = spec
+            .regex
+            .map(|pattern| {
+                RegexBuilder::new(&pattern)
+                    .case_insensitive(true)
+                    .build()
+                    .with_context(|| format!("regex が不正です: {}", pattern))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            name,
+            glob,
+            regex,
+            extensions: spec
+                .extension
+                .iter()
+                .map(|ext| ext.trim_start_matches('.').to_lowercase())
+                .collect(),
+            min_size: spec.min_size.as_ref().map(SizeSpec::bytes).transpose()?,
+            max_size: spec.max_size.as_ref().map(SizeSpec::bytes).transpose()?,
+            modified_after: spec.modified_after.as_deref().map(parse_date).transpose()?,
+            modified_before: spec.modified_before.as_deref().map(parse_date).transpose()?,
+            older_than_days: spec.older_than_days,
+            newer_than_days: spec.newer_than_days,
+            source,
+            target: spec.target.as_deref().map(Template::parse).transpose()?,
+            rename: spec.rename.as_deref().map(Template::parse).transpose()?,
+        })
+    }
+
+    /// 条件に合わない理由（すべて合えば None）
+    fn mismatch(&self, root: &Path, file: &FileInfo) -> Option<String> {
+        if !self.extensions.is_empty() && !self.extensions.contains(&file.extension) {
+            return Some(format!(
+                "拡張子が {} ではありません（{}）",
+                self.extensions.join(" / "),
+                file.extension
+            ));
+        }
+        if let Some((pattern, matcher)) = &self.glob {
+            // `/` を含むパターンはルートからの相対パスと照合する
+            let subject = if pattern.contains('/') {
+                RoutingRules::relative(root, &file.path).unwrap_or_default()
+            } else {
+                file.name.clone()
+            };
+            if !matcher.is_match(&subject) {
+                return Some(format!("glob {} に一致しません", pattern));
+            }
+        }
+        if let Some(regex) = &self.regex
+            && !regex.is_match(&file.name)
+        {
+            return Some(format!("regex {} に一致しません", regex.as_str()));
+        }
+        if let Some(min) = self.min_size
+            && file.size < min
+        {
+            return Some(format!(
+                "サイズが {} 未満です（{}）",
+                UI::format_size(min),
+                UI::format_size(file.size)
+            ));
+        }
+        if let Some(max) = self.max_size
+            && file.size > max
+        {
+            return Some(format!(
+                "サイズが {} を超えています（{}）",
+                UI::format_size(max),
+                UI::format_size(file.size)
+            ));
+        }
+        let date = file.modified.date_naive();
+        if let Some(after) = self.modified_after
+            && date < after
+        {
+            return Some(format!("更新日 {} が {} より前です", date, after));
+        }
+        if let Some(before) = self.modified_before
+            && date >= before
+        {
+            return Some(format!("更新日 {} が {} 以降です", date, before));
+        }
+        let age_days = (Local::now() - file.modified).num_days().max(0) as u64;
+        if let Some(days) = self.older_than_days
+            && age_days < days
+        {
+            return Some(format!("{} 日前のファイルです（{} 日以上前が条件）", age_days, days));
+        }
+        if let Some(days) = self.newer_than_days
+            && age_days >= days
+        {
+            return Some(format!("{} 日前のファイルです（{} 日未満が条件）", age_days, days));
+        }
+        if let Some((pattern, matcher)) = &self.source {
+            let parent = file
+                .path
+                .parent()
+                .and_then(|parent| RoutingRules::relative(root, parent));
+            // 指定したフォルダの中（サブフォルダを含む）にあれば一致
+            let inside = |parent: &str| {
+                let mut current = parent;
+                loop {
+                    if matcher.is_match(current) {
+                        return true;
+                    }
+                    match current.rsplit_once('/') {
+                        Some((head, _)) => current = head,
+                        None => return false,
+                    }
+                }
+            };
+            match parent {
+                Some(parent) if inside(&parent) => {}
+                Some(parent) => {
+                    return Some(format!("場所 {} が source {} に一致しません", parent, pattern));
+                }
+                None => return Some("ルートの外にあるファイルです".to_string()),
+            }
+        }
+        None
+    }
+
+    fn route(&self, root: &Path, file: &FileInfo) -> Result<Route> {
+        if self.target.is_none() && self.rename.is_none() {
+            return Ok(Route::Keep);
+        }
+
+        let folder = match &self.target {
+            Some(template) => {
+                let rendered = template.render(file);
+                let relative = PathBuf::from(&rendered);
+                let inside = relative
+                    .components()
+                    .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+                if !inside {
+                    bail!(
+                        "ルール {} の target はルートからの相対パスで指定してください: {}（{}）",
+                        self.name,
+                        rendered,
+                        template.source
+                    );
+                }
+                root.join(relative)
+            }
+            None => file
+                .path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| root.to_path_buf()),
+        };
+
+        let file_name = match &self.rename {
+            Some(template) => {
+                let rendered = template.render(file);
+                if rendered.is_empty() || rendered.contains(['/', '\\']) || rendered == ".." {
+                    bail!(
+                        "ルール {} の rename はファイル名だけを指定してください: {:?}（{}）",
+                        self.name,
+                        rendered,
+                        template.source
+                    );
+                }
+                rendered
+            }
+            None => file.name.clone(),
+        };
+
+        Ok(Route::Move { folder, file_name })
+    }
+}
+
+/// `YYYY-MM-DD` の日付
+fn parse_date(text: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .map_err(|_| anyhow!("日付は YYYY-MM-DD で指定してください: {:?}", text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Duration, TimeZone};
+
+    use crate::test_support::TempDir;
+
+    fn file(root: &Path, relative: &str, size: u64, modified: DateTime<Local>) -> FileInfo {
+        let path = root.join(relative);
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        FileInfo {
+            path,
+            name,
+            extension,
+            size,
+            modified,
+            is_dir: false,
+        }
+    }
+
+    fn at(year: i32, month: u32, day: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(year, month, day, 5, 6, 7).unwrap()
+    }
+
+    fn rules(root: &Path, toml: &str) -> Result<RoutingRules> {
+        let path = RoutingRules::path(root);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, toml).unwrap();
+        RoutingRules::load(root)
+    }
+
+    #[test]
+    fn parse_size_reads_units() {
+        let cases: [(&str, Option<u64>); 12] = [
+            ("0", Some(0)),
+            ("512", Some(512)),
+            ("512B", Some(512)),
+            ("1k", Some(1 << 10)),
+            ("50MB", Some(50 << 20)),
+            (" 1.5 GB ", Some(3 << 29)),
+            ("2tb", Some(2 << 40)),
+            ("", None),
+            ("MB", None),
+            ("1.2.3MB", None),
+            ("10XB", None),
+            ("-1", None),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(parse_size(text).ok(), expected, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn template_parses_and_renders() {
+        let root = Path::new("/root");
+        let clip = file(root, "clip.MP4", 0, at(2020, 3, 4));
+        let cases = [
+            ("{name}", "clip.MP4"),
+            ("{stem}_{dd}.{ext}", "clip_04.mp4"),
+            ("{yyyy}/{yyyymm}", "2020/202003"),
+            ("{mm}-{yyyymmdd}", "03-20200304"),
+            ("{timestamp}", "20200304050607"),
+            ("{period}", "2020/202003"),
+            ("plain/text", "plain/text"),
+        ];
+        for (source, expected) in cases {
+            let template = Template::parse(source).unwrap();
+            assert_eq!(template.render(&clip), expected, "{}", source);
+        }
+
+        // 今年のファイルは YYYY/ を付けない
+        let now = Local::now();
+        let recent = file(root, "clip.mp4", 0, now);
+        let period = Template::parse("{period}").unwrap().render(&recent);
+        assert_eq!(period, now.format("%Y%m").to_string());
+
+        for source in ["{unknown}", "{name", "a/{YYYY}", "{}"] {
+            assert!(Template::parse(source).is_err(), "{}", source);
+        }
+    }
+
+    #[test]
+    fn conditions_match_files() {
+        let root = Path::new("/root");
+        let big = 10 << 20;
+        let old = at(2020, 3, 4);
+        let cases: [(RuleSpec, FileInfo, bool); 16] = [
+            (RuleSpec { glob: Some("*.mp4".into()), ..Default::default() }, file(root, "record/CLIP.MP4", 0, old), true),
+            (RuleSpec { glob: Some("*.mp4".into()), ..Default::default() }, file(root, "record/clip.mov", 0, old), false),
+            (RuleSpec { glob: Some("record/*.mp4".into()), ..Default::default() }, file(root, "record/clip.mp4", 0, old), true),
+            (RuleSpec { glob: Some("record/*.mp4".into()), ..Default::default() }, file(root, "record/sub/clip.mp4", 0, old), false),
+            (RuleSpec { extension: vec![".MP4".into()], ..Default::default() }, file(root, "clip.mp4", 0, old), true),
+            (RuleSpec { regex: Some("^obs_\\d+".into()), ..Default::default() }, file(root, "OBS_01.mkv", 0, old), true),
+            (RuleSpec { source: Some("/record/".into()), ..Default::default() }, file(root, "record/2020/clip.mp4", 0, old), true),
+            (RuleSpec { source: Some("record".into()), ..Default::default() }, file(root, "downloads/clip.mp4", 0, old), false),
+            (RuleSpec { source: Some("record".into()), ..Default::default() }, file(Path::new("/elsewhere"), "record/clip.mp4", 0, old), false),
+            (RuleSpec { min_size: Some(SizeSpec::Text("10MB".into())), ..Default::default() }, file(root, "clip.mp4", big, old), true),
+            (RuleSpec { min_size: Some(SizeSpec::Bytes(big + 1)), ..Default::default() }, file(root, "clip.mp4", big, old), false),
+            (RuleSpec { max_size: Some(SizeSpec::Bytes(big)), ..Default::default() }, file(root, "clip.mp4", big, old), true),
+            (RuleSpec { modified_after: Some("2020-03-04".into()), ..Default::default() }, file(root, "clip.mp4", 0, old), true),
+            (RuleSpec { modified_before: Some("2020-03-04".into()), ..Default::default() }, file(root, "clip.mp4", 0, old), false),
+            (RuleSpec { older_than_days: Some(30), ..Default::default() }, file(root, "clip.mp4", 0, Local::now() - Duration::days(10)), false),
+            (RuleSpec { newer_than_days: Some(30), ..Default::default() }, file(root, "clip.mp4", 0, Local::now() - Duration::days(10)), true),
+        ];
+        for (index, (spec, info, expected)) in cases.into_iter().enumerate() {
+            let rule = RoutingRule::compile(format!("#{}", index + 1), spec).unwrap();
+            assert_eq!(rule.mismatch(root, &info).is_none(), expected, "#{} {}", index + 1, info.path.display());
+        }
+    }
+
+    #[test]
+    fn route_uses_the_first_matching_rule() {
+        let root = TempDir::new("routing-rules-route");
+        let rules = rules(
+            &root,
+            r#"
+[[rule]]
+name = "keep-big"
+min-size = "1GB"
+
+[[rule]]
+name = "clips"
+extension = ["mp4"]
+target = "2_assets/footage/{yyyy}"
+rename = "{yyyymmdd}_{name}"
+
+[[rule]]
+name = "all-mp4"
+glob = "*.mp4"
+target = "other"
+"#,
+        )
+        .unwrap();
+        assert_eq!(rules.len(), 3);
+
+        let clip = file(&root, "record/clip.mp4", 10, at(2020, 3, 4));
+        let matched = rules.route(&clip).unwrap().unwrap();
+        assert_eq!(matched.rule, "clips");
+        assert_eq!(
+            matched.route,
+            Route::Move {
+                folder: root.join("2_assets/footage/2020"),
+                file_name: "20200304_clip.mp4".to_string(),
+            }
+        );
+
+        let big = file(&root, "record/clip.mp4", 1 << 30, at(2020, 3, 4));
+        let matched = rules.route(&big).unwrap().unwrap();
+        assert_eq!((matched.rule.as_str(), matched.route), ("keep-big", Route::Keep));
+
+        let note = file(&root, "record/note.txt", 10, at(2020, 3, 4));
+        assert!(rules.route(&note).unwrap().is_none());
+
+        // 一致したルールまでの評価結果
+        let traces = rules.explain(&clip);
+        let names: Vec<&str> = traces.iter().map(|trace| trace.rule.as_str()).collect();
+        assert_eq!(names, ["keep-big", "clips"]);
+        assert!(traces[0].mismatch.is_some());
+        assert!(traces[1].mismatch.is_none());
+
+        let traces = rules.explain(&note);
+        assert_eq!(traces.len(), 3);
+        assert!(traces.iter().all(|trace| trace.mismatch.is_some()));
+    }
+
+    #[test]
+    fn route_rejects_targets_outside_the_root() {
+        let root = TempDir::new("routing-rules-escape");
+        let clip = file(&root, "record/clip.mp4", 10, at(2020, 3, 4));
+        let cases = [
+            ("target = \"../outside\"", false),
+            ("target = \"/abs/{yyyy}\"", false),
+            ("target = \"a/../../{yyyy}\"", false),
+            ("rename = \"sub/{name}\"", false),
+            ("rename = \"..\"", false),
+            ("target = \"./archive/{yyyy}\"", true),
+        ];
+        for (line, ok) in cases {
+            let rules = rules(&root, &format!("[[rule]]\n{}\n", line)).unwrap();
+            assert_eq!(rules.route(&clip).is_ok(), ok, "{}", line);
+        }
+    }
+
+    #[test]
+    fn load_rejects_invalid_rules() {
+        let root = TempDir::new("routing-rules-invalid");
+        assert!(rules(&root, "").unwrap().is_empty());
+        for toml in [
+            "[[rule]]\nmin-size = \"10XB\"\n",
+            "[[rule]]\ntarget = \"{unknown}\"\n",
+            "[[rule]]\nmodified-after = \"2020/03/04\"\n",
+            "[[rule]]\nglob = \"[\"\n",
+            "[[rule]]\ncolor = \"red\"\n",
+        ] {
+            assert!(rules(&root, toml).is_err(), "{}", toml);
+        }
+    }
+}
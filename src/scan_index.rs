@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::looker_ignore::LookerIgnore;
+use crate::scanner::FileInfo;
+
+/// `<root>/.looker/scan-index.json` の走査結果のキャッシュ
+///
+/// フォルダごとに中身（名前・サイズ・更新日時）を記録し、次回はフォルダの
+/// 更新日時が変わっていなければ中のファイルを読み直さずに記録を使う。
+/// record の日付は更新日時から決めるので、これで整理に必要な情報はすべて揃う。
+/// フォルダの更新日時はファイルの追加・削除・名前の変更でしか変わらないため、
+/// 既存のファイルを上書きしただけの変更は拾えない（`--rescan` で読み直す）。
+///
+/// 複製しても同じキャッシュを共有する。`uncached` は何も記録しない。
+/// `.lookerignore` と OS が作るファイルは記録には残すが、列挙の結果からは除く。
+#[derive(Debug, Clone)]
+pub struct ScanIndex {
+    inner: Option<Arc<IndexState>>,
+    ignore: LookerIgnore,
+}
+
+#[derive(Debug)]
+struct IndexState {
+    root: PathBuf,
+    /// true なら記録を使わずにすべて読み直す（結果は記録し直す）
+    rescan: bool,
+    file: Mutex<IndexFile>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IndexFile {
+    version: u32,
+    /// キーはルートからの相対パス（`/` 区切り、ルート自身は空文字）
+    dirs: BTreeMap<String, IndexedDir>,
+    #[serde(skip)]
+    dirty: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedDir {
+    /// フォルダの更新日時（UNIX 時刻のナノ秒）
+    mtime: u64,
+    /// 読み取った時刻（UNIX 時刻のナノ秒）
+    scanned_at: u64,
+    entries: Vec<IndexedEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedEntry {
+    name: String,
+    size: u64,
+    mtime: Option<u64>,
+    is_dir: bool,
+}
+
+const INDEX_VERSION: u32 = 2;
+
+/// 更新日時の精度が粗いファイルシステム（FAT は 2 秒）で、読み取りと同じ時刻に
+/// 起きた変更を見逃さないための余裕
+const MTIME_GRACE: Duration = Duration::from_secs(2);
+
+impl ScanIndex {
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(".looker").join("scan-index.json")
+    }
+
+    /// キャッシュを読み込む（無い・壊れている・形式が古い場合は空から始める）
+    pub fn open(root: &Path, rescan: bool) -> Self {
+        let file = fs::read_to_string(Self::path(root))
+            .ok()
+            .and_then(|content| serde_json::from_str::<IndexFile>(&content).ok())
+            .filter(|file| file.version == INDEX_VERSION)
+            .unwrap_or_else(|| IndexFile {
+                version: INDEX_VERSION,
+                ..IndexFile::default()
+            });
+
+        Self {
+            inner: Some(Arc::new(IndexState {
+                root: root.to_path_buf(),
+                rescan,
+                file: Mutex::new(file),
+            })),
+            ignore: LookerIgnore::new(root),
+        }
+    }
+
+    /// 記録を使わずに毎回読み取る（除外の判定にはルートを使う）
+    pub fn uncached(root: &Path) -> Self {
+        Self {
+            inner: None,
+            ignore: LookerIgnore::new(root),
+        }
+    }
+
+    /// フォルダ直下のエントリを名前順に列挙する（フォルダも含む、除外したものは含まない）
+    pub fn list(&self, dir: &Path) -> Result<Vec<FileInfo>> {
+        let entries = self.list_all(dir)?;
+        Ok(entries
+            .into_iter()
+            .filter(|entry| !self.ignore.is_ignored(&entry.path, entry.is_dir))
+            .collect())
+    }
+
+    fn list_all(&self, dir: &Path) -> Result<Vec<FileInfo>> {
+        let Some(state) = &self.inner else {
+            return Self::read_dir(dir).map(|(_, entries)| Self::to_file_infos(dir, entries));
+        };
+
+        let key = state.key(dir);
+        let mtime = fs::metadata(dir).ok().and_then(|meta| to_nanos(meta.modified().ok()));
+        if !state.rescan
+            && let Some(mtime) = mtime
+        {
+            let file = state.file.lock().unwrap();
+            if let Some(cached) = file.dirs.get(&key)
+                && cached.mtime == mtime
+                && from_nanos(cached.mtime) + MTIME_GRACE <= from_nanos(cached.scanned_at)
+            {
+                return Ok(Self::to_file_infos(dir, cached.entries.clone()));
+            }
+        }
+
+        let (scanned_at, entries) = Self::read_dir(dir)?;
+        let mut file = state.file.lock().unwrap();
+        match mtime {
+            Some(mtime) => {
+                file.dirs.insert(
+                    key,
+                    IndexedDir {
+                        mtime,
+                        scanned_at,
+                        entries: entries.clone(),
+                    },
+                );
+            }
+            None => {
+                file.dirs.remove(&key);
+            }
+        }
+        file.dirty = true;
+        Ok(Self::to_file_infos(dir, entries))
+    }
+
+    /// 変更があればキャッシュを書き出す（無くなったフォルダの記録は捨てる）
+    pub fn save(&self) -> Result<()> {
+        let Some(state) = &self.inner else {
+            return Ok(());
+        };
+        let mut file = state.file.lock().unwrap();
+        if !file.dirty {
+            return Ok(());
+        }
+        let root = state.root.clone();
+        file.dirs
+            .retain(|key, _| root.join(key.replace('/', std::path::MAIN_SEPARATOR_STR)).is_dir());
+
+        let path = Self::path(&root);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("フォルダの作成に失敗: {}", parent.display()))?;
+        }
+        let content = serde_json::to_string(&*file)?;
+        // 書き込み途中で止まっても壊れたキャッシュが残らないように置き換える
+        let temp = path.with_extension("json.tmp");
+        fs::write(&temp, content)
+            .and_then(|_| fs::rename(&temp, &path))
+            .with_context(|| format!("走査キャッシュの書き込みに失敗: {}", path.display()))?;
+        file.dirty = false;
+        Ok(())
+    }
+
+    fn read_dir(dir: &Path) -> Result<(u64, Vec<IndexedEntry>)> {
+        let scanned_at = to_nanos(Some(SystemTime::now())).unwrap_or_default();
+        let dir_entries: Vec<fs::DirEntry> = fs::read_dir(dir)
+            .with_context(|| format!("ディレクトリの読み取りに失敗: {:?}", dir))?
+            .filter_map(|entry| entry.ok())
+            .collect();
+        // ファイルの多いフォルダでは 1 件ずつの stat が一番時間がかかるので並列に取る
+        let mut entries: Vec<IndexedEntry> = dir_entries
+            .par_iter()
+            .filter_map(|entry| {
+                // シンボリックリンクはたどらない
+                let metadata = entry.metadata().ok()?;
+                Some(IndexedEntry {
+                    name: entry.file_name().to_string_lossy().to_string(),
+                    size: metadata.len(),
+                    mtime: to_nanos(metadata.modified().ok()),
+                    is_dir: metadata.is_dir(),
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok((scanned_at, entries))
+    }
+
+    fn to_file_infos(dir: &Path, entries: Vec<IndexedEntry>) -> Vec<FileInfo> {
+        entries
+            .into_iter()
+            .map(|entry| {
+                FileInfo::new(
+                    dir.join(&entry.name),
+                    entry.size,
+                    entry.mtime.map(from_nanos),
+                    entry.is_dir,
+                )
+            })
+            .collect()
+    }
+}
+
+impl IndexState {
+    fn key(&self, dir: &Path) -> String {
+        match dir.strip_prefix(&self.root) {
+            Ok(relative) => relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/"),
+            Err(_) => dir.to_string_lossy().to_string(),
+        }
+    }
+}
+
+fn to_nanos(time: Option<SystemTime>) -> Option<u64> {
+    let since_epoch = time?.duration_since(UNIX_EPOCH).ok()?;
+    u64::try_from(since_epoch.as_nanos()).ok()
+}
+
+fn from_nanos(nanos: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_nanos(nanos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn temp_root(name: &str) -> TempDir {
+        let root = TempDir::new(name);
+        fs::create_dir_all(root.join("record")).unwrap();
+        root
+    }
+
+    /// フォルダの更新日時を過去にして、記録が使える状態にする
+    fn age_dir(dir: &Path) {
+        let past = SystemTime::now() - Duration::from_secs(60);
+        fs::File::open(dir).unwrap().set_modified(past).unwrap();
+    }
+
+    fn sizes(index: &ScanIndex, dir: &Path) -> Vec<(String, u64)> {
+        index
+            .list(dir)
+            .unwrap()
+            .into_iter()
+            .map(|info| (info.name, info.size))
+            .collect()
+    }
+
+    #[test]
+    fn unchanged_dir_uses_the_cached_entries() {
+        let root = temp_root("index-hit");
+        let dir = root.join("record");
+        fs::write(dir.join("a.png"), b"1").unwrap();
+        age_dir(&dir);
+
+        let index = ScanIndex::open(&root, false);
+        assert_eq!(sizes(&index, &dir), vec![("a.png".to_string(), 1)]);
+        // 上書きではフォルダの更新日時が変わらないので記録のまま
+        fs::write(dir.join("a.png"), b"123").unwrap();
+        assert_eq!(sizes(&index, &dir), vec![("a.png".to_string(), 1)]);
+
+        // 保存した記録も同じように使われ、--rescan なら読み直す
+        index.save().unwrap();
+        assert_eq!(sizes(&ScanIndex::open(&root, false), &dir), vec![("a.png".to_string(), 1)]);
+        assert_eq!(sizes(&ScanIndex::open(&root, true), &dir), vec![("a.png".to_string(), 3)]);
+    }
+
+    #[test]
+    fn changed_dir_mtime_invalidates_the_entry() {
+        let root = temp_root("index-invalidate");
+        let dir = root.join("record");
+        fs::write(dir.join("a.png"), b"1").unwrap();
+        age_dir(&dir);
+
+        let index = ScanIndex::open(&root, false);
+        assert_eq!(sizes(&index, &dir).len(), 1);
+        fs::write(dir.join("a.png"), b"123").unwrap();
+        fs::write(dir.join("b.png"), b"").unwrap();
+        assert_eq!(
+            sizes(&index, &dir),
+            vec![("a.png".to_string(), 3), ("b.png".to_string(), 0)]
+        );
+    }
+
+    #[test]
+    fn recently_modified_dir_is_read_again() {
+        let root = temp_root("index-grace");
+        let dir = root.join("record");
+        // 読み取りと同じ頃に更新されたフォルダは、更新日時が同じでも記録を使わない
+        fs::write(dir.join("a.png"), b"1").unwrap();
+
+        let index = ScanIndex::open(&root, false);
+        assert_eq!(sizes(&index, &dir), vec![("a.png".to_string(), 1)]);
+        fs::write(dir.join("a.png"), b"123").unwrap();
+        assert_eq!(sizes(&index, &dir), vec![("a.png".to_string(), 3)]);
+    }
+}
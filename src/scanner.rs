@@ -1,7 +1,12 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use chrono::{DateTime, Local};
+use std::fs;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+use std::time::SystemTime;
+
+use rayon::prelude::*;
+
+use crate::scan_index::ScanIndex;
 
 /// スキャン結果
 #[derive(Debug, Clone)]
@@ -9,52 +14,78 @@ pub struct FileInfo {
     pub path: PathBuf,
     pub name: String,
     pub extension: String,
-    #[allow(dead_code)]
     pub size: u64,
     pub modified: DateTime<Local>,
     pub is_dir: bool,
 }
 
+impl FileInfo {
+    /// 更新日時が取れない場合は現在時刻にする
+    pub fn new(path: PathBuf, size: u64, modified: Option<SystemTime>, is_dir: bool) -> Self {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+
+        Self {
+            path,
+            name,
+            extension,
+            size,
+            modified: modified.map(DateTime::<Local>::from).unwrap_or_else(Local::now),
+            is_dir,
+        }
+    }
+}
+
 /// ディレクトリを走査するシンプルなラッパー
 pub struct DriveScanner;
 
 impl DriveScanner {
-    /// 指定パス以下を再帰的に列挙
-    pub fn scan(path: &Path) -> Result<Vec<FileInfo>> {
-        let mut files = Vec::new();
-
-        for entry in WalkDir::new(path)
-            .follow_links(false)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            let metadata = entry
-                .metadata()
-                .with_context(|| format!("メタデータ取得に失敗: {:?}", entry.path()))?;
-
-            let modified = metadata
-                .modified()
-                .map(DateTime::<Local>::from)
-                .unwrap_or_else(|_| Local::now());
-
-            let path = entry.path().to_path_buf();
-            let name = entry.file_name().to_string_lossy().to_string();
-            let extension = path
-                .extension()
-                .and_then(|e| e.to_str())
-                .unwrap_or("")
-                .to_lowercase();
-
-            files.push(FileInfo {
-                path,
-                name,
-                extension,
-                size: metadata.len(),
-                modified,
-                is_dir: metadata.is_dir(),
-            });
-        }
+    /// 指定パス以下を再帰的に列挙（指定パス自身も含む）
+    ///
+    /// 各フォルダの中身は `index` を通して読むので、前回から変わっていない
+    /// フォルダは読み直さない。読めないフォルダは飛ばす。
+    pub fn scan(path: &Path, index: &ScanIndex) -> Result<Vec<FileInfo>> {
+        let Ok(metadata) = fs::symlink_metadata(path) else {
+            return Ok(Vec::new());
+        };
+        let root = FileInfo::new(
+            path.to_path_buf(),
+            metadata.len(),
+            metadata.modified().ok(),
+            metadata.is_dir(),
+        );
 
+        let mut files = vec![root];
+        if files[0].is_dir {
+            files.extend(Self::walk(path, index));
+        }
         Ok(files)
     }
+
+    /// フォルダの中身を名前順に、サブフォルダはその直後に中身を並べる
+    ///
+    /// サブフォルダは並列に読むが、結果の順番は実行ごとに変わらない。
+    fn walk(dir: &Path, index: &ScanIndex) -> Vec<FileInfo> {
+        let Ok(entries) = index.list(dir) else {
+            return Vec::new();
+        };
+        entries
+            .into_par_iter()
+            .flat_map_iter(|entry| {
+                let children = if entry.is_dir {
+                    Self::walk(&entry.path, index)
+                } else {
+                    Vec::new()
+                };
+                std::iter::once(entry).chain(children)
+            })
+            .collect()
+    }
 }
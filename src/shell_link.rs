@@ -0,0 +1,431 @@
+use std::fs;
+use std::path::{Component, Path, Prefix};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+/// Windows のショートカット（MS-SHLLINK 形式の .lnk）を読み書きする
+///
+/// LinkTargetIDList は持たず、LinkInfo のローカルパスと StringData だけで
+/// リンク先を表現する。Explorer はこの形式でも解決できるため、管理者権限や
+/// COM を使わずにどの OS 上でも生成できる。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellLink {
+    /// リンク先の絶対パス（Windows 形式: `D:\1_projects\...`）
+    pub target: String,
+    /// リンクの説明（Explorer のコメント欄）
+    pub description: Option<String>,
+    /// .lnk の場所から見たリンク先の相対パス
+    pub relative_path: Option<String>,
+    /// 作業フォルダ
+    pub working_dir: Option<String>,
+}
+
+const HEADER_SIZE: u32 = 0x4C;
+const LINK_CLSID: [u8; 16] = [
+    0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46,
+];
+
+const HAS_LINK_TARGET_ID_LIST: u32 = 0x0000_0001;
+const HAS_LINK_INFO: u32 = 0x0000_0002;
+const HAS_NAME: u32 = 0x0000_0004;
+const HAS_RELATIVE_PATH: u32 = 0x0000_0008;
+const HAS_WORKING_DIR: u32 = 0x0000_0010;
+const HAS_ARGUMENTS: u32 = 0x0000_0020;
+const HAS_ICON_LOCATION: u32 = 0x0000_0040;
+const IS_UNICODE: u32 = 0x0000_0080;
+
+const FILE_ATTRIBUTE_ARCHIVE: u32 = 0x0000_0020;
+const SW_SHOWNORMAL: u32 = 1;
+const VOLUME_ID_AND_LOCAL_BASE_PATH: u32 = 0x0000_0001;
+const DRIVE_FIXED: u32 = 3;
+/// LocalBasePathOffsetUnicode を含む LinkInfo ヘッダのサイズ
+const LINK_INFO_HEADER_SIZE: u32 = 0x24;
+
+impl ShellLink {
+    /// 指定したファイルを指すショートカットを組み立てる
+    pub fn for_target(target: &Path) -> Self {
+        Self {
+            target: windows_path(target),
+            description: None,
+            relative_path: None,
+            working_dir: target.parent().map(windows_path),
+        }
+    }
+
+    /// .lnk ファイルとして書き出す
+    pub fn write(&self, link_path: &Path) -> Result<()> {
+        fs::write(link_path, self.to_bytes())
+            .with_context(|| format!("ショートカットの書き込みに失敗: {}", link_path.display()))
+    }
+
+    /// .lnk ファイルを読み込む
+    pub fn read(link_path: &Path) -> Result<Self> {
+        let data = fs::read(link_path)
+            .with_context(|| format!("ショートカットの読み込みに失敗: {}", link_path.display()))?;
+        Self::from_bytes(&data)
+            .with_context(|| format!("ショートカットの解析に失敗: {}", link_path.display()))
+    }
+
+    /// MS-SHLLINK のバイト列に変換
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut flags = HAS_LINK_INFO | IS_UNICODE;
+        if self.description.is_some() {
+            flags |= HAS_NAME;
+        }
+        if self.relative_path.is_some() {
+            flags |= HAS_RELATIVE_PATH;
+        }
+        if self.working_dir.is_some() {
+            flags |= HAS_WORKING_DIR;
+        }
+
+        let mut out = Vec::new();
+
+        // ShellLinkHeader
+        put_u32(&mut out, HEADER_SIZE);
+        out.extend_from_slice(&LINK_CLSID);
+        put_u32(&mut out, flags);
+        put_u32(&mut out, FILE_ATTRIBUTE_ARCHIVE);
+        out.extend_from_slice(&[0u8; 24]); // CreationTime / AccessTime / WriteTime
+        put_u32(&mut out, 0); // FileSize
+        put_u32(&mut out, 0); // IconIndex
+        put_u32(&mut out, SW_SHOWNORMAL);
+        out.extend_from_slice(&[0u8; 2]); // HotKey
+        out.extend_from_slice(&[0u8; 10]); // Reserved1-3
+
+        // LinkInfo
+        out.extend_from_slice(&Self::link_info(&self.target));
+
+        // StringData
+        for value in [&self.description, &self.relative_path, &self.working_dir]
+            .into_iter()
+            .flatten()
+        {
+            let units: Vec<u16> = value.encode_utf16().collect();
+            put_u16(&mut out, units.len() as u16);
+            for unit in units {
+                put_u16(&mut out, unit);
+            }
+        }
+
+        // ExtraData は TerminalBlock のみ
+        put_u32(&mut out, 0);
+        out
+    }
+
+    /// MS-SHLLINK のバイト列を解析
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let mut reader = Reader::new(data);
+
+        if reader.u32()? != HEADER_SIZE {
+            bail!("ShellLinkHeader のサイズが不正です");
+        }
+        if reader.bytes(16)? != LINK_CLSID {
+            bail!("ShellLink の CLSID が一致しません");
+        }
+        let flags = reader.u32()?;
+        reader.skip(HEADER_SIZE as usize - 24)?;
+
+        if flags & HAS_LINK_TARGET_ID_LIST != 0 {
+            let size = reader.u16()? as usize;
+            reader.skip(size)?;
+        }
+
+        let mut target = None;
+        if flags & HAS_LINK_INFO != 0 {
+            let start = reader.pos;
+            let size = reader.u32()? as usize;
+            let info = data
+                .get(start..start + size)
+                .ok_or_else(|| anyhow!("LinkInfo が途中で途切れています"))?;
+            target = Self::parse_link_info(info)?;
+            reader.pos = start + size;
+        }
+
+        let unicode = flags & IS_UNICODE != 0;
+        let mut read_string = |flag: u32| -> Result<Option<String>> {
+            if flags & flag == 0 {
+                return Ok(None);
+            }
+            let count = reader.u16()? as usize;
+            if unicode {
+                let units = (0..count)
+                    .map(|_| reader.u16())
+                    .collect::<Result<Vec<u16>>>()?;
+                Ok(Some(String::from_utf16_lossy(&units)))
+            } else {
+                Ok(Some(String::from_utf8_lossy(reader.bytes(count)?).into_owned()))
+            }
+        };
+
+        let description = read_string(HAS_NAME)?;
+        let relative_path = read_string(HAS_RELATIVE_PATH)?;
+        let working_dir = read_string(HAS_WORKING_DIR)?;
+        read_string(HAS_ARGUMENTS)?;
+        read_string(HAS_ICON_LOCATION)?;
+
+        let target = target.ok_or_else(|| anyhow!("リンク先のローカルパスがありません"))?;
+        Ok(Self {
+            target,
+            description,
+            relative_path,
+            working_dir,
+        })
+    }
+
+    /// VolumeID と LocalBasePath を持つ LinkInfo を組み立てる
+    fn link_info(target: &str) -> Vec<u8> {
+        // VolumeID（ラベルは空文字列）
+        let mut volume_id = Vec::new();
+        put_u32(&mut volume_id, 0x11);
+        put_u32(&mut volume_id, DRIVE_FIXED);
+        put_u32(&mut volume_id, 0); // DriveSerialNumber
+        put_u32(&mut volume_id, 0x10); // VolumeLabelOffset
+        volume_id.push(0);
+
+        // ANSI 版は ASCII 以外を '?' に置き換える（Unicode 版が優先される）
+        let mut local_base_path: Vec<u8> = target
+            .chars()
+            .map(|c| if c.is_ascii() { c as u8 } else { b'?' })
+            .collect();
+        local_base_path.push(0);
+
+        let mut local_base_path_unicode = Vec::new();
+        for unit in target.encode_utf16().chain(std::iter::once(0)) {
+            put_u16(&mut local_base_path_unicode, unit);
+        }
+
+        let volume_id_offset = LINK_INFO_HEADER_SIZE;
+        let local_base_path_offset = volume_id_offset + volume_id.len() as u32;
+        let common_path_suffix_offset = local_base_path_offset + local_base_path.len() as u32;
+        let local_base_path_offset_unicode = common_path_suffix_offset + 1;
+        let common_path_suffix_offset_unicode =
+            local_base_path_offset_unicode + local_base_path_unicode.len() as u32;
+        let total_size = common_path_suffix_offset_unicode + 2;
+
+        let mut info = Vec::with_capacity(total_size as usize);
+        put_u32(&mut info, total_size);
+        put_u32(&mut info, LINK_INFO_HEADER_SIZE);
+        put_u32(&mut info, VOLUME_ID_AND_LOCAL_BASE_PATH);
+        put_u32(&mut info, volume_id_offset);
+        put_u32(&mut info, local_base_path_offset);
+        put_u32(&mut info, 0); // CommonNetworkRelativeLinkOffset
+        put_u32(&mut info, common_path_suffix_offset);
+        put_u32(&mut info, local_base_path_offset_unicode);
+        put_u32(&mut info, common_path_suffix_offset_unicode);
+        info.extend_from_slice(&volume_id);
+        info.extend_from_slice(&local_base_path);
+        info.push(0); // CommonPathSuffix
+        info.extend_from_slice(&local_base_path_unicode);
+        put_u16(&mut info, 0); // CommonPathSuffixUnicode
+        info
+    }
+
+    /// LinkInfo からローカルパス（LocalBasePath + CommonPathSuffix）を取り出す
+    fn parse_link_info(info: &[u8]) -> Result<Option<String>> {
+        let mut reader = Reader::new(info);
+        reader.skip(4)?; // LinkInfoSize
+        let header_size = reader.u32()?;
+        let info_flags = reader.u32()?;
+        if info_flags & VOLUME_ID_AND_LOCAL_BASE_PATH == 0 {
+            return Ok(None);
+        }
+        reader.skip(4)?; // VolumeIDOffset
+        let local_base_path_offset = reader.u32()? as usize;
+        reader.skip(4)?; // CommonNetworkRelativeLinkOffset
+        let common_path_suffix_offset = reader.u32()? as usize;
+
+        if header_size >= LINK_INFO_HEADER_SIZE {
+            let base_offset = reader.u32()? as usize;
+            let suffix_offset = reader.u32()? as usize;
+            if base_offset != 0 {
+                let mut path = utf16_z(info, base_offset)?;
+                if suffix_offset != 0 {
+                    path.push_str(&utf16_z(info, suffix_offset)?);
+                }
+                return Ok(Some(path));
+            }
+        }
+
+        let mut path = ansi_z(info, local_base_path_offset)?;
+        path.push_str(&ansi_z(info, common_path_suffix_offset)?);
+        Ok(Some(path))
+    }
+}
+
+/// パスを Windows 形式の文字列に変換する
+///
+/// WSL の `/mnt/d/...` はドライブレター `D:\...` に読み替える。
+pub fn windows_path(path: &Path) -> String {
+    let mut components = path.components().peekable();
+    if let Some(Component::Prefix(prefix)) = components.peek()
+        && let Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) = prefix.kind()
+    {
+        let rest: Vec<String> = path
+            .components()
+            .skip(1)
+            .filter(|c| !matches!(c, Component::RootDir))
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        return format!("{}:\\{}", letter as char, rest.join("\\"));
+    }
+
+    let parts: Vec<String> = path
+        .components()
+        .filter(|c| !matches!(c, Component::RootDir))
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+
+    if path.has_root()
+        && parts.len() >= 2
+        && parts[0] == "mnt"
+        && parts[1].len() == 1
+        && parts[1].chars().all(|c| c.is_ascii_alphabetic())
+    {
+        return format!(
+            "{}:\\{}",
+            parts[1].to_ascii_uppercase(),
+            parts[2..].join("\\")
+        );
+    }
+
+    let joined = parts.join("\\");
+    if path.has_root() {
+        format!("\\{joined}")
+    } else {
+        joined
+    }
+}
+
+fn put_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+/// NUL 終端の ANSI 文字列を読む
+fn ansi_z(data: &[u8], offset: usize) -> Result<String> {
+    let rest = data
+        .get(offset..)
+        .ok_or_else(|| anyhow!("文字列オフセットが範囲外です"))?;
+    let end = rest.iter().position(|b| *b == 0).unwrap_or(rest.len());
+    Ok(String::from_utf8_lossy(&rest[..end]).into_owned())
+}
+
+/// NUL 終端の UTF-16LE 文字列を読む
+fn utf16_z(data: &[u8], offset: usize) -> Result<String> {
+    let rest = data
+        .get(offset..)
+        .ok_or_else(|| anyhow!("文字列オフセットが範囲外です"))?;
+    let units: Vec<u16> = rest
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .take_while(|unit| *unit != 0)
+        .collect();
+    Ok(String::from_utf16_lossy(&units))
+}
+
+/// リトルエンディアンの簡易リーダー
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let slice = self
+            .data
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| anyhow!("ショートカットのデータが途中で途切れています"))?;
+        self.pos += len;
+        Ok(slice)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<()> {
+        self.bytes(len).map(|_| ())
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let b = self.bytes(2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let b = self.bytes(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ShellLink {
+        ShellLink {
+            target: "D:\\1_projects\\20240301_promo\\出力\\final.mp4".to_string(),
+            description: Some("20240301_promo".to_string()),
+            relative_path: Some("..\\1_projects\\20240301_promo\\出力\\final.mp4".to_string()),
+            working_dir: Some("D:\\1_projects\\20240301_promo\\出力".to_string()),
+        }
+    }
+
+    #[test]
+    fn round_trip_keeps_all_fields() {
+        let link = sample();
+        assert_eq!(ShellLink::from_bytes(&link.to_bytes()).unwrap(), link);
+    }
+
+    #[test]
+    fn round_trip_without_string_data() {
+        let link = ShellLink {
+            description: None,
+            relative_path: None,
+            working_dir: None,
+            ..sample()
+        };
+        assert_eq!(ShellLink::from_bytes(&link.to_bytes()).unwrap(), link);
+    }
+
+    #[test]
+    fn header_has_fixed_size_and_clsid() {
+        let bytes = sample().to_bytes();
+        assert_eq!(&bytes[0..4], &[0x4C, 0x00, 0x00, 0x00]);
+        assert_eq!(
+            &bytes[4..20],
+            &[
+                0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x46
+            ]
+        );
+        let flags = u32::from_le_bytes(bytes[20..24].try_into().unwrap());
+        assert_eq!(
+            flags,
+            HAS_LINK_INFO | HAS_NAME | HAS_RELATIVE_PATH | HAS_WORKING_DIR | IS_UNICODE
+        );
+        // TerminalBlock
+        assert_eq!(&bytes[bytes.len() - 4..], &[0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn rejects_wrong_clsid() {
+        let mut bytes = sample().to_bytes();
+        bytes[4] ^= 0xFF;
+        assert!(ShellLink::from_bytes(&bytes).is_err());
+        assert!(ShellLink::from_bytes(&bytes[..10]).is_err());
+    }
+
+    #[test]
+    fn windows_path_maps_wsl_mounts() {
+        assert_eq!(
+            windows_path(Path::new("/mnt/d/1_projects/a.mp4")),
+            "D:\\1_projects\\a.mp4"
+        );
+        assert_eq!(windows_path(Path::new("/home/user/a.mp4")), "\\home\\user\\a.mp4");
+    }
+}
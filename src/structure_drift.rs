@@ -0,0 +1,420 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::folder_note::FolderNotes;
+use crate::looker_ignore::LookerIgnore;
+use crate::naming::NamingRule;
+use crate::structure_manager::StructureDefinition;
+use crate::ui::UI;
+
+/// 標準フォルダ構造に無いフォルダ・ファイルの検出
+pub struct StructureDrift;
+
+/// 標準フォルダとの違いの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftReason {
+    /// 大文字小文字・全角半角だけの違い（`0_Inbox`）
+    Case,
+    /// 空白・ハイフンなど区切り文字の違い（`0 inbox`）
+    Separator,
+    /// 先頭の番号の付け忘れ（`projects`）
+    Prefix,
+    /// 先頭の番号の違い（`2_projects`）
+    Number,
+    /// 綴りの違い（`graphics`）
+    Typo,
+}
+
+impl DriftReason {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DriftReason::Case => "大文字小文字・全角半角の違い",
+            DriftReason::Separator => "区切り文字の違い",
+            DriftReason::Prefix => "番号の付け忘れ",
+            DriftReason::Number => "番号の違い",
+            DriftReason::Typo => "綴りの違い",
+        }
+    }
+
+    /// `--apply` でまとめてよい違いか
+    ///
+    /// 綴りの違いは別の意味のフォルダ（`memo` と `meme`）を、番号の違いは意図して分けた
+    /// フォルダ（`2_projects`）を拾うことがあるので候補の表示だけにする。
+    pub fn can_merge(&self) -> bool {
+        !matches!(self, DriftReason::Typo | DriftReason::Number)
+    }
+}
+
+/// 標準フォルダ構造に無いエントリ
+#[derive(Debug, Clone)]
+pub struct DriftEntry {
+    pub path: PathBuf,
+    pub is_dir: bool,
+    /// 最も近い標準フォルダ（ルートからの相対パス）とその理由（綴りの違いは候補のみ）
+    pub suggestion: Option<(String, DriftReason)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftMergeMode {
+    /// 標準フォルダが無いので名前を変える
+    Rename,
+    /// 標準フォルダに中身を移して空になったフォルダを消す
+    Merge,
+}
+
+/// 近い名前のフォルダを標準フォルダにまとめる計画
+#[derive(Debug, Clone)]
+pub struct DriftMerge {
+    pub source: PathBuf,
+    pub target: PathBuf,
+    pub mode: DriftMergeMode,
+}
+
+#[derive(Debug)]
+pub struct DriftReport {
+    pub root: PathBuf,
+    pub profile: String,
+    /// 確認した階層の数
+    pub levels: usize,
+    pub entries: Vec<DriftEntry>,
+    pub merges: Vec<DriftMerge>,
+}
+
+/// 近い名前とみなす一致度
+const SIMILARITY_THRESHOLD: f64 = 0.75;
+
+impl StructureDrift {
+    /// 子フォルダが定義されている各階層で、定義に無いエントリを探す
+    pub fn plan(root: &Path, definition: &StructureDefinition) -> Result<DriftReport> {
+        // 階層（親フォルダ）ごとの標準フォルダ名
+        let mut levels: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        levels.insert(String::new(), Vec::new());
+        for entry in &definition.entries {
+            let (parent, name) = match entry.path.rsplit_once('/') {
+                Some((parent, name)) => (parent.to_string(), name.to_string()),
+                None => (String::new(), entry.path.clone()),
+            };
+            levels.entry(parent).or_default().push(name);
+        }
+
+        let mut report = DriftReport {
+            root: root.to_path_buf(),
+            profile: definition.profile.clone(),
+            levels: 0,
+            entries: Vec::new(),
+            merges: Vec::new(),
+        };
+        // 名前の変更で作られる予定の標準フォルダ
+        let mut renamed: BTreeSet<PathBuf> = BTreeSet::new();
+        let ignore = LookerIgnore::new(root);
+
+        for (level, expected) in &levels {
+            let dir = if level.is_empty() {
+                root.to_path_buf()
+            } else {
+                root.join(level)
+            };
+            let Ok(read_dir) = fs::read_dir(&dir) else {
+                continue;
+            };
+            report.levels += 1;
+
+            let mut children: Vec<(String, bool)> = read_dir
+                .filter_map(|entry| entry.ok())
+                .map(|entry| {
+                    let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+                    (entry.file_name().to_string_lossy().to_string(), is_dir)
+                })
+                .filter(|(name, is_dir)| {
+                    !Self::is_ignored(name) && !ignore.is_ignored(&dir.join(name), *is_dir)
+                })
+                .collect();
+            children.sort();
+
+            let template = definition.template_for(level);
+            for (name, is_dir) in children {
+                if expected.contains(&name) {
+                    continue;
+                }
+                // カテゴリのテンプレートに合うフォルダは標準フォルダと同じに扱う
+                if is_dir && template.is_some_and(|template| template.accepts(&name)) {
+                    continue;
+                }
+                let path = dir.join(&name);
+                let suggestion = if is_dir {
+                    Self::closest(&name, expected).map(|(standard, reason)| {
+                        let relative = if level.is_empty() {
+                            standard
+                        } else {
+                            format!("{}/{}", level, standard)
+                        };
+                        (relative, reason)
+                    })
+                } else {
+                    None
+                };
+
+                if let Some((standard, reason)) = &suggestion
+                    && reason.can_merge()
+                {
+                    let target = root.join(standard);
+                    let exists = (target.exists() && !Self::same_entry(&path, &target))
+                        || renamed.contains(&target);
+                    let mode = if exists {
+                        DriftMergeMode::Merge
+                    } else {
+                        renamed.insert(target.clone());
+                        DriftMergeMode::Rename
+                    };
+                    report.merges.push(DriftMerge {
+                        source: path.clone(),
+                        target,
+                        mode,
+                    });
+                }
+
+                report.entries.push(DriftEntry {
+                    path,
+                    is_dir,
+                    suggestion,
+                });
+            }
+        }
+
+        // 名前の変更を先に行い、まとめる先のフォルダを用意する
+        report
+            .merges
+            .sort_by_key(|merge| merge.mode == DriftMergeMode::Merge);
+        Ok(report)
+    }
+
+    /// 最も近い標準フォルダ名
+    fn closest(name: &str, expected: &[String]) -> Option<(String, DriftReason)> {
+        let folded = |s: &str| s.nfkc().flat_map(char::to_lowercase).collect::<String>();
+        let stripped = |s: &str| Self::strip_number(s).to_string();
+
+        let mut best: Option<(f64, String, DriftReason)> = None;
+        for candidate in expected {
+            let (score, reason) = if folded(name) == folded(candidate) {
+                (1.0, DriftReason::Case)
+            } else if NamingRule::normalize_name(name) == NamingRule::normalize_name(candidate) {
+                (0.95, DriftReason::Separator)
+            } else if NamingRule::normalize_name(&stripped(name))
+                == NamingRule::normalize_name(&stripped(candidate))
+            {
+                let reason = if Self::strip_number(name) == name {
+                    DriftReason::Prefix
+                } else {
+                    DriftReason::Number
+                };
+                (0.9, reason)
+            } else {
+                let similarity = NamingRule::name_similarity(name, candidate)
+                    .max(NamingRule::name_similarity(&stripped(name), &stripped(candidate)));
+                if similarity < SIMILARITY_THRESHOLD {
+                    continue;
+                }
+                (similarity * 0.85, DriftReason::Typo)
+            };
+            if best.as_ref().is_none_or(|(best_score, _, _)| score > *best_score) {
+                best = Some((score, candidate.clone(), reason));
+            }
+        }
+        best.map(|(_, candidate, reason)| (candidate, reason))
+    }
+
+    /// `1_projects` の `1_` のような番号を取り除く
+    fn strip_number(name: &str) -> &str {
+        match name.split_once('_') {
+            Some((number, rest))
+                if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) =>
+            {
+                rest
+            }
+            _ => name,
+        }
+    }
+
+    /// 隠しファイルや Looker が作るファイル（OS が作るものは `LookerIgnore` で除く）
+    fn is_ignored(name: &str) -> bool {
+        name.starts_with('.') || name.starts_with('$') || FolderNotes::is_note_file(name)
+    }
+
+    /// 大文字小文字を区別しないファイルシステムで同じフォルダを指しているか
+    fn same_entry(a: &Path, b: &Path) -> bool {
+        match (a.canonicalize(), b.canonicalize()) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// 計画を適用する（衝突したファイルは元の場所に残す）
+    pub fn apply(report: &DriftReport) -> Result<()> {
+        let mut moved = 0usize;
+        let mut conflicts = Vec::new();
+
+        for merge in &report.merges {
+            match merge.mode {
+                DriftMergeMode::Rename if !merge.target.exists() => {
+                    fs::rename(&merge.source, &merge.target).with_context(|| {
+                        format!("名前の変更に失敗: {:?} -> {:?}", merge.source, merge.target)
+                    })?;
+                    UI::info(&format!(
+                        "  名前を変更: {} → {}",
+                        merge.source.display(),
+                        merge.target.display()
+                    ));
+                }
+                DriftMergeMode::Rename if Self::same_entry(&merge.source, &merge.target) => {
+                    // 大文字小文字だけの変更は一時的な名前を経由する
+                    let temporary = merge.source.with_extension("looker-rename");
+                    fs::rename(&merge.source, &temporary)
+                        .and_then(|_| fs::rename(&temporary, &merge.target))
+                        .with_context(|| {
+                            format!("名前の変更に失敗: {:?} -> {:?}", merge.source, merge.target)
+                        })?;
+                    UI::info(&format!(
+                        "  名前を変更: {} → {}",
+                        merge.source.display(),
+                        merge.target.display()
+                    ));
+                }
+                _ => {
+                    Self::merge_dir(&merge.source, &merge.target, &mut moved, &mut conflicts)?;
+                    if fs::remove_dir(&merge.source).is_ok() {
+                        UI::info(&format!(
+                            "  まとめました: {} → {}",
+                            merge.source.display(),
+                            merge.target.display()
+                        ));
+                    }
+                }
+            }
+        }
+
+        for conflict in &conflicts {
+            UI::warning(&format!("  同名のファイルがあるため残しました: {}", conflict.display()));
+        }
+        UI::success(&format!(
+            "フォルダ {} 件を処理しました（移動 {} 件 / 衝突 {} 件）",
+            report.merges.len(),
+            moved,
+            conflicts.len()
+        ));
+        Ok(())
+    }
+
+    /// `source` の中身を `target` に移す（フォルダ同士は中身をまとめる）
+    fn merge_dir(
+        source: &Path,
+        target: &Path,
+        moved: &mut usize,
+        conflicts: &mut Vec<PathBuf>,
+    ) -> Result<()> {
+        fs::create_dir_all(target)
+            .with_context(|| format!("フォルダの作成に失敗: {}", target.display()))?;
+        let mut entries: Vec<PathBuf> = fs::read_dir(source)
+            .with_context(|| format!("ディレクトリの読み取りに失敗: {:?}", source))?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .collect();
+        entries.sort();
+
+        for entry in entries {
+            let Some(name) = entry.file_name() else {
+                continue;
+            };
+            let destination = target.join(name);
+            if !destination.exists() {
+                fs::rename(&entry, &destination).with_context(|| {
+                    format!("移動に失敗: {:?} -> {:?}", entry, destination)
+                })?;
+                *moved += 1;
+            } else if entry.is_dir() && destination.is_dir() {
+                Self::merge_dir(&entry, &destination, moved, conflicts)?;
+                let _ = fs::remove_dir(&entry);
+            } else {
+                conflicts.push(entry);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structure_manager::StructureEntry;
+    use crate::test_support::TempDir;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn closest_classifies_the_difference() {
+        let expected = names(&["0_inbox", "1_projects", "3_docs"]);
+        let closest = |name| StructureDrift::closest(name, &expected);
+
+        assert_eq!(closest("0_Inbox"), Some(("0_inbox".to_string(), DriftReason::Case)));
+        assert_eq!(closest("３_DOCS"), Some(("3_docs".to_string(), DriftReason::Case)));
+        assert_eq!(closest("0 inbox"), Some(("0_inbox".to_string(), DriftReason::Separator)));
+        assert_eq!(closest("projects"), Some(("1_projects".to_string(), DriftReason::Prefix)));
+        assert_eq!(closest("2_projects"), Some(("1_projects".to_string(), DriftReason::Number)));
+        assert_eq!(closest("1_inbox"), Some(("0_inbox".to_string(), DriftReason::Number)));
+        assert_eq!(closest("1_projcts"), Some(("1_projects".to_string(), DriftReason::Typo)));
+        assert_eq!(closest("music"), None);
+    }
+
+    #[test]
+    fn closest_reports_near_words_as_typos() {
+        let expected = names(&["meme", "document", "icon"]);
+        for (name, standard) in [("memo", "meme"), ("documents", "document"), ("icons", "icon")] {
+            assert_eq!(
+                StructureDrift::closest(name, &expected),
+                Some((standard.to_string(), DriftReason::Typo))
+            );
+        }
+    }
+
+    #[test]
+    fn typos_and_other_numbers_are_suggested_but_not_merged() {
+        let root = TempDir::new("drift");
+        for dir in ["meme", "memo", "0_Inbox", "projects", "2_projects"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        let definition = StructureDefinition {
+            profile: "test".to_string(),
+            entries: ["0_inbox", "meme", "1_projects"]
+                .into_iter()
+                .map(|path| StructureEntry {
+                    path: path.to_string(),
+                    description: None,
+                })
+                .collect(),
+            templates: Vec::new(),
+        };
+
+        let report = StructureDrift::plan(&root, &definition).unwrap();
+        let memo = report
+            .entries
+            .iter()
+            .find(|entry| entry.path == root.join("memo"))
+            .unwrap();
+        assert_eq!(memo.suggestion, Some(("meme".to_string(), DriftReason::Typo)));
+        let numbered = report
+            .entries
+            .iter()
+            .find(|entry| entry.path == root.join("2_projects"))
+            .unwrap();
+        assert_eq!(numbered.suggestion, Some(("1_projects".to_string(), DriftReason::Number)));
+
+        let mut sources: Vec<&Path> = report.merges.iter().map(|merge| merge.source.as_path()).collect();
+        sources.sort();
+        assert_eq!(sources, vec![root.join("0_Inbox").as_path(), root.join("projects").as_path()]);
+    }
+}
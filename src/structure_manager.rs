@@ -1,62 +1,400 @@
+use std::fmt::Write as _;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde::Serialize;
 
+use crate::folder_note::{FolderNote, FolderNoteStatus, FolderNotes};
+use crate::looker_ignore::LookerIgnore;
 use crate::ui::UI;
 
 /// フォルダ構造を管理するマネージャー
 pub struct StructureManager;
 
+/// 組み込みの標準フォルダ構造（プロファイル名 `default`）
+const DEFAULT_STRUCTURE: &str = include_str!("default_structure.tree");
+
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// 標準フォルダ構造の 1 フォルダ
+#[derive(Debug, Clone)]
+pub struct StructureEntry {
+    /// ルートからの相対パス（`/` 区切り）
+    pub path: String,
+    pub description: Option<String>,
+}
+
+/// プロファイルから読み込んだ標準フォルダ構造
+#[derive(Debug, Clone)]
+pub struct StructureDefinition {
+    pub profile: String,
+    pub entries: Vec<StructureEntry>,
+    pub templates: Vec<CategoryTemplate>,
+}
+
+/// 名前が決まっていないカテゴリの階層（`1_projects/[category]` など）
+#[derive(Debug, Clone)]
+pub struct CategoryTemplate {
+    /// カテゴリを置くフォルダ（ルートからの相対パス）
+    pub parent: String,
+    pub name: String,
+    pub rule: CategoryRule,
+    pub description: Option<String>,
+}
+
+/// カテゴリ名の決まり
+#[derive(Debug, Clone)]
+pub enum CategoryRule {
+    /// `[category]`: どんな名前でもよい
+    Any,
+    /// `[category: work, club]`: 決まった名前だけ
+    Names(Vec<String>),
+    /// `[category: /^[a-z]+$/]`: 正規表現に一致する名前
+    Pattern(Regex),
+}
+
+impl CategoryTemplate {
+    /// `1_projects/[category]` の形の表示名
+    pub fn label(&self) -> String {
+        format!("{}/[{}]", self.parent, self.name)
+    }
+
+    /// カテゴリとして使える名前か（`_uncategorized` など `_` で始まる作業用フォルダは常に使える）
+    pub fn accepts(&self, name: &str) -> bool {
+        name.starts_with('_')
+            || match &self.rule {
+                CategoryRule::Any => true,
+                CategoryRule::Names(names) => names.iter().any(|allowed| allowed == name),
+                CategoryRule::Pattern(pattern) => pattern.is_match(name),
+            }
+    }
+}
+
+/// ツリー形式のファイル 1 行分
+struct TreeLine {
+    /// フォルダのパス（テンプレートの行では置き場所のフォルダ）
+    path: String,
+    description: Option<String>,
+    /// `!name` で親プロファイルのフォルダを取り除く
+    remove: bool,
+    /// `[name: rule]` のカテゴリのテンプレート
+    template: Option<(String, CategoryRule)>,
+}
+
+impl StructureDefinition {
+    /// 既存のフォルダの説明を更新するか、親フォルダの最後の子の後ろに追加する
+    fn insert(&mut self, path: &str, description: Option<String>) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.path == path) {
+            if description.is_some() {
+                entry.description = description;
+            }
+            return;
+        }
+        // 親フォルダが無ければ先に追加する
+        let parent = path.rsplit_once('/').map(|(parent, _)| parent);
+        if let Some(parent) = parent {
+            self.insert(parent, None);
+        }
+        let position = match parent {
+            Some(parent) => self
+                .entries
+                .iter()
+                .rposition(|entry| Self::is_within(&entry.path, parent))
+                .map(|index| index + 1)
+                .unwrap_or(self.entries.len()),
+            None => self.entries.len(),
+        };
+        self.entries.insert(
+            position,
+            StructureEntry {
+                path: path.to_string(),
+                description,
+            },
+        );
+    }
+
+    /// フォルダとその中身を取り除く
+    fn remove(&mut self, path: &str) {
+        self.entries.retain(|entry| !Self::is_within(&entry.path, path));
+        self.templates
+            .retain(|template| !Self::is_within(&template.parent, path));
+    }
+
+    /// フォルダのカテゴリのテンプレートを置き換える
+    fn insert_template(&mut self, template: CategoryTemplate) {
+        self.insert(&template.parent, None);
+        self.templates.retain(|other| other.parent != template.parent);
+        self.templates.push(template);
+    }
+
+    /// フォルダ直下のカテゴリのテンプレート
+    pub fn template_for(&self, parent: &str) -> Option<&CategoryTemplate> {
+        self.templates
+            .iter()
+            .find(|template| template.parent == parent)
+    }
+
+    fn apply(&mut self, lines: Vec<TreeLine>) {
+        for line in lines {
+            match (line.remove, line.template) {
+                (true, Some(_)) => self.templates.retain(|template| template.parent != line.path),
+                (true, None) => self.remove(&line.path),
+                (false, Some((name, rule))) => self.insert_template(CategoryTemplate {
+                    parent: line.path,
+                    name,
+                    rule,
+                    description: line.description,
+                }),
+                (false, None) => self.insert(&line.path, line.description),
+            }
+        }
+    }
+
+    /// `path` が `folder` 自身かその中にあるか
+    fn is_within(path: &str, folder: &str) -> bool {
+        path == folder
+            || path
+                .strip_prefix(folder)
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+}
+
 impl StructureManager {
-    /// 標準フォルダ構造の定義
-    fn standard_structure() -> Vec<&'static str> {
-        vec![
-            "0_inbox",
-            "0_inbox/downloads",
-            "0_inbox/record",
-            "0_inbox/record/screen capture",
-            "0_inbox/record/screen record",
-            "0_inbox/record/voice record",
-            "1_projects",
-            "2_assets",
-            "2_assets/footage",
-            "2_assets/graphic",
-            "2_assets/photo",
-            "2_assets/illust",
-            "2_assets/bgm",
-            "2_assets/sfx",
-            "3_docs",
-            "3_docs/profile",
-            "3_docs/collection",
-            "3_docs/class",
-            "3_docs/cclub",
-            "3_docs/guide",
-            "3_docs/family",
-            "3_docs/icon",
-            "3_docs/meme",
-            "4_apps",
-            "5_gallery",
-            "9_archive",
-        ]
+    /// カスタムプロファイルの置き場所（`<root>/.looker/structures/<name>.tree`）
+    pub fn profile_path(root: &Path, profile: &str) -> PathBuf {
+        root.join(".looker")
+            .join("structures")
+            .join(format!("{}.tree", profile))
+    }
+
+    /// プロファイルの標準フォルダ構造を読み込む
+    ///
+    /// `default` は組み込みの構造（`.looker/structures/default.tree` があればそちらを優先）。
+    /// ファイルの先頭に `@extends <name>` を書くと、そのプロファイルに追加・上書きする。
+    pub fn load(root: &Path, profile: &str) -> Result<StructureDefinition> {
+        Self::load_profile(root, profile, &mut Vec::new())
+    }
+
+    fn load_profile(root: &Path, profile: &str, chain: &mut Vec<String>) -> Result<StructureDefinition> {
+        if chain.iter().any(|name| name == profile) {
+            bail!(
+                "プロファイルの継承が循環しています: {} -> {}",
+                chain.join(" -> "),
+                profile
+            );
+        }
+        chain.push(profile.to_string());
+
+        let path = Self::profile_path(root, profile);
+        let (source, content) = if path.exists() {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("構造ファイルの読み込みに失敗: {}", path.display()))?;
+            (path.display().to_string(), content)
+        } else if profile == DEFAULT_PROFILE {
+            ("（組み込み）".to_string(), DEFAULT_STRUCTURE.to_string())
+        } else {
+            bail!("プロファイルが見つかりません: {}（{}）", profile, path.display());
+        };
+
+        let (extends, lines) = Self::parse_tree(&content)
+            .with_context(|| format!("構造ファイルの解析に失敗: {}", source))?;
+
+        let mut definition = match extends {
+            // default.tree 自身が default を継承する場合は組み込みの構造を使う
+            Some(base) if base == DEFAULT_PROFILE && profile == DEFAULT_PROFILE => {
+                Self::parse_builtin()?
+            }
+            Some(base) => Self::load_profile(root, &base, chain)?,
+            None => StructureDefinition {
+                profile: profile.to_string(),
+                entries: Vec::new(),
+                templates: Vec::new(),
+            },
+        };
+        definition.profile = profile.to_string();
+        definition.apply(lines);
+        Ok(definition)
+    }
+
+    fn parse_builtin() -> Result<StructureDefinition> {
+        let (_, lines) = Self::parse_tree(DEFAULT_STRUCTURE)?;
+        let mut definition = StructureDefinition {
+            profile: DEFAULT_PROFILE.to_string(),
+            entries: Vec::new(),
+            templates: Vec::new(),
+        };
+        definition.apply(lines);
+        Ok(definition)
+    }
+
+    /// ツリー形式のテキストを解析する
+    ///
+    /// インデント（空白・タブ・罫線）の深さで親子関係を決め、` #` 以降を説明として扱う。
+    /// `[name]` の行は名前の決まっていないカテゴリの階層になる。
+    fn parse_tree(content: &str) -> Result<(Option<String>, Vec<TreeLine>)> {
+        let mut extends = None;
+        let mut lines = Vec::new();
+        // (インデント, パス, テンプレートの行か)
+        let mut stack: Vec<(usize, String, bool)> = Vec::new();
+
+        for (number, raw) in content.lines().enumerate() {
+            let indent = raw
+                .chars()
+                .take_while(|c| matches!(c, ' ' | '\t' | '│' | '├' | '└' | '─'))
+                .count();
+            let text: String = raw.chars().skip(indent).collect();
+            let text = text.trim_end();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            if let Some(base) = text.strip_prefix("@extends") {
+                let base = base.trim();
+                if base.is_empty() || !lines.is_empty() || extends.is_some() {
+                    bail!("{} 行目: @extends はファイルの先頭に 1 つだけ書けます", number + 1);
+                }
+                extends = Some(base.to_string());
+                continue;
+            }
+
+            let (name, description) = match text.find(" #").or_else(|| text.find("\t#")) {
+                Some(index) => (
+                    text[..index].trim(),
+                    Some(text[index + 2..].trim().to_string()).filter(|d| !d.is_empty()),
+                ),
+                None => (text, None),
+            };
+            let (remove, name) = match name.strip_prefix('!') {
+                Some(name) => (true, name.trim()),
+                None => (false, name),
+            };
+            let name = name.trim_end_matches('/');
+            // 最後の区切りの後ろが `[...]` ならカテゴリのテンプレート
+            let (parent, last) = Self::split_last_segment(name);
+            let (name, template) = match last.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+                Some(text) => {
+                    let template = Self::parse_template(text)
+                        .with_context(|| format!("{} 行目: テンプレートが不正です: {:?}", number + 1, name))?;
+                    (parent, Some(template))
+                }
+                None => (name, None),
+            };
+            let segments: Vec<&str> = if name.is_empty() && template.is_some() {
+                Vec::new()
+            } else {
+                name.split('/').map(str::trim).collect()
+            };
+            if segments
+                .iter()
+                .any(|segment| segment.is_empty() || *segment == "." || *segment == ".." || segment.contains('\\'))
+            {
+                bail!("{} 行目: フォルダ名が不正です: {:?}", number + 1, name);
+            }
+
+            while stack.last().is_some_and(|(depth, _, _)| *depth >= indent) {
+                stack.pop();
+            }
+            let path = match stack.last() {
+                Some((_, _, true)) => {
+                    bail!("{} 行目: カテゴリのテンプレートの下にはフォルダを書けません", number + 1)
+                }
+                Some((_, parent, _)) if segments.is_empty() => parent.clone(),
+                Some((_, parent, _)) => format!("{}/{}", parent, segments.join("/")),
+                None => segments.join("/"),
+            };
+            if path.is_empty() {
+                bail!("{} 行目: カテゴリのテンプレートはフォルダの中に書いてください", number + 1);
+            }
+            stack.push((indent, path.clone(), template.is_some()));
+            lines.push(TreeLine {
+                path,
+                description,
+                remove,
+                template,
+            });
+        }
+
+        Ok((extends, lines))
+    }
+
+    /// 最後の `/` で親と最後の要素に分ける（`[...]` の中の `/` は区切りとみなさない）
+    ///
+    /// `1_projects/[category: /^[a-z]+$/]` は `1_projects` と `[category: /^[a-z]+$/]` になる。
+    fn split_last_segment(name: &str) -> (&str, &str) {
+        let mut depth = 0usize;
+        let mut escaped = false;
+        let mut last_separator = None;
+        for (index, c) in name.char_indices() {
+            if escaped {
+                escaped = false;
+                continue;
+            }
+            match c {
+                '\\' if depth > 0 => escaped = true,
+                '[' => depth += 1,
+                ']' => depth = depth.saturating_sub(1),
+                '/' if depth == 0 => last_separator = Some(index),
+                _ => {}
+            }
+        }
+        match last_separator {
+            Some(index) => (&name[..index], &name[index + 1..]),
+            None => ("", name),
+        }
+    }
+
+    /// `name: rule` を読む（rule は省略・`a, b` の名前の一覧・`/正規表現/`）
+    fn parse_template(text: &str) -> Result<(String, CategoryRule)> {
+        let (name, rule) = match text.split_once(':') {
+            Some((name, rule)) => (name.trim(), rule.trim()),
+            None => (text.trim(), ""),
+        };
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-'))
+        {
+            bail!("名前が不正です: {:?}", name);
+        }
+
+        let rule = if rule.is_empty() {
+            CategoryRule::Any
+        } else if let Some(pattern) = rule
+            .strip_prefix('/')
+            .and_then(|rule| rule.strip_suffix('/'))
+        {
+            CategoryRule::Pattern(
+                Regex::new(pattern).with_context(|| format!("正規表現が不正です: {}", pattern))?,
+            )
+        } else {
+            CategoryRule::Names(
+                rule.split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            )
+        };
+        Ok((name.to_string(), rule))
     }
 
     /// 標準フォルダ構造を検証して不足しているフォルダを作成
-    pub fn ensure_standard_structure(root: &Path) -> Result<()> {
+    pub fn ensure_standard_structure(root: &Path, definition: &StructureDefinition) -> Result<()> {
         UI::info(&format!("ルートディレクトリ: {}", root.display()));
+        UI::info(&format!("プロファイル: {}", definition.profile));
         UI::info("標準フォルダ構造を確認しています...\n");
 
-        let structure = Self::standard_structure();
         let mut missing_folders = Vec::new();
         let mut existing_folders = Vec::new();
 
         // 既存のフォルダと不足しているフォルダを確認
-        for folder_path in structure {
-            let full_path = root.join(folder_path);
+        for entry in &definition.entries {
+            let full_path = root.join(&entry.path);
             if full_path.exists() {
-                existing_folders.push(folder_path);
+                existing_folders.push(entry.path.as_str());
             } else {
-                missing_folders.push(folder_path);
+                missing_folders.push(entry);
             }
         }
 
@@ -75,29 +413,47 @@ impl StructureManager {
 
         UI::warning(&format!("\n不足しているフォルダ: {} 件", missing_folders.len()));
         for folder in &missing_folders {
-            UI::warning(&format!("  ✗ {}", folder));
+            match &folder.description {
+                Some(description) => {
+                    UI::warning(&format!("  ✗ {}  # {}", folder.path, description))
+                }
+                None => UI::warning(&format!("  ✗ {}", folder.path)),
+            }
         }
 
         // 不足しているフォルダを作成
         UI::info(&format!("\n{} 件のフォルダを作成します...", missing_folders.len()));
         let mut created = 0;
         
-        for folder_path in missing_folders {
-            let full_path = root.join(folder_path);
+        for folder in missing_folders {
+            let full_path = root.join(&folder.path);
             fs::create_dir_all(&full_path)
                 .with_context(|| format!("フォルダの作成に失敗: {}", full_path.display()))?;
-            UI::info(&format!("  作成: {}", folder_path));
+            UI::info(&format!("  作成: {}", folder.path));
             created += 1;
         }
 
         UI::success(&format!("\n{} 件のフォルダを作成しました。", created));
+        if !definition.templates.is_empty() {
+            UI::info("\n次の場所にはカテゴリごとのフォルダを作って使います:");
+            for template in &definition.templates {
+                match &template.description {
+                    Some(description) => {
+                        UI::info(&format!("  {}  # {}", template.label(), description))
+                    }
+                    None => UI::info(&format!("  {}", template.label())),
+                }
+            }
+        }
         Ok(())
     }
 
     /// フォルダ構造の検証のみ（作成はしない）
-    #[allow(dead_code)]
-    pub fn validate_structure(root: &Path) -> Result<StructureValidationResult> {
-        let structure = Self::standard_structure();
+    pub fn validate_structure(
+        root: &Path,
+        definition: &StructureDefinition,
+    ) -> Result<StructureValidationResult> {
+        let structure = definition.entries.iter().map(|entry| entry.path.as_str());
         let mut missing = Vec::new();
         let mut existing = Vec::new();
 
@@ -111,32 +467,264 @@ impl StructureManager {
         }
 
         Ok(StructureValidationResult {
+            root: root.to_path_buf(),
+            profile: definition.profile.clone(),
             total: existing.len() + missing.len(),
             existing,
             missing,
+            category_issues: Self::check_categories(root, definition),
         })
     }
+
+    /// カテゴリの階層に直接置かれたファイルと、決まりに合わないカテゴリ名を探す
+    fn check_categories(root: &Path, definition: &StructureDefinition) -> Vec<CategoryIssue> {
+        let mut issues = Vec::new();
+        let ignore = LookerIgnore::new(root);
+
+        for template in &definition.templates {
+            let Ok(read_dir) = fs::read_dir(root.join(&template.parent)) else {
+                continue;
+            };
+            let mut children: Vec<(PathBuf, String, bool)> = read_dir
+                .filter_map(|entry| entry.ok())
+                .map(|entry| {
+                    let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    (entry.path(), name, is_dir)
+                })
+                .filter(|(path, name, is_dir)| {
+                    !Self::is_ignored(name) && !ignore.is_ignored(path, *is_dir)
+                })
+                .collect();
+            children.sort();
+
+            for (path, name, is_dir) in children {
+                let kind = if !is_dir {
+                    CategoryIssueKind::LooseFile
+                } else if template.accepts(&name)
+                    || definition
+                        .entries
+                        .iter()
+                        .any(|entry| entry.path == format!("{}/{}", template.parent, name))
+                {
+                    continue;
+                } else {
+                    CategoryIssueKind::UnknownCategory
+                };
+                issues.push(CategoryIssue {
+                    path,
+                    kind,
+                    template: template.label(),
+                });
+            }
+        }
+
+        issues
+    }
+
+    /// 隠しファイルや Looker が作るファイル（OS が作るものは `LookerIgnore` で除く）
+    fn is_ignored(name: &str) -> bool {
+        name.starts_with('.') || FolderNotes::is_note_file(name)
+    }
+}
+
+/// カテゴリの階層で見つかった問題の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CategoryIssueKind {
+    /// カテゴリフォルダに入っていないファイル（`2_assets/footage/clip.mp4`）
+    LooseFile,
+    /// 決まりに合わないカテゴリ名
+    UnknownCategory,
+}
+
+impl CategoryIssueKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CategoryIssueKind::LooseFile => "カテゴリ外のファイル",
+            CategoryIssueKind::UnknownCategory => "決まりに無いカテゴリ",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CategoryIssue {
+    pub path: PathBuf,
+    pub kind: CategoryIssueKind,
+    /// `2_assets/footage/[category]` の形のテンプレート
+    pub template: String,
 }
 
-#[allow(dead_code)]
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct StructureValidationResult {
+    pub root: PathBuf,
+    pub profile: String,
     pub total: usize,
     pub existing: Vec<String>,
     pub missing: Vec<String>,
+    pub category_issues: Vec<CategoryIssue>,
 }
 
 impl StructureValidationResult {
-    #[allow(dead_code)]
     pub fn is_complete(&self) -> bool {
         self.missing.is_empty()
     }
 
-    #[allow(dead_code)]
     pub fn completion_rate(&self) -> f64 {
         if self.total == 0 {
             return 0.0;
         }
         (self.existing.len() as f64 / self.total as f64) * 100.0
     }
+
+    /// Markdown のレポートにする（`--check-structure --format markdown`）
+    pub fn to_markdown(&self, definition: &StructureDefinition, notes: &[FolderNote]) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# 標準フォルダ構造の確認\n");
+        let _ = writeln!(
+            out,
+            "- ルート: `{}`\n- プロファイル: {}\n- 完成度: {:.1}%（{} / {}）\n",
+            self.root.display(),
+            self.profile,
+            self.completion_rate(),
+            self.existing.len(),
+            self.total
+        );
+
+        if !self.category_issues.is_empty() {
+            let _ = writeln!(out, "## カテゴリの問題\n");
+            let _ = writeln!(out, "| パス | 問題 | テンプレート |\n| --- | --- | --- |");
+            for issue in &self.category_issues {
+                let _ = writeln!(
+                    out,
+                    "| `{}` | {} | `{}` |",
+                    issue.path.display(),
+                    issue.kind.label(),
+                    issue.template
+                );
+            }
+            out.push('\n');
+        }
+
+        if !self.missing.is_empty() {
+            let _ = writeln!(out, "## 不足しているフォルダ\n");
+            for path in &self.missing {
+                let description = definition
+                    .entries
+                    .iter()
+                    .find(|entry| &entry.path == path)
+                    .and_then(|entry| entry.description.as_deref());
+                match description {
+                    Some(description) => {
+                        let _ = writeln!(out, "- `{}` — {}", path, description);
+                    }
+                    None => {
+                        let _ = writeln!(out, "- `{}`", path);
+                    }
+                }
+            }
+            out.push('\n');
+        }
+
+        let changed: Vec<&FolderNote> = notes
+            .iter()
+            .filter(|note| note.status != FolderNoteStatus::Unchanged)
+            .collect();
+        if !changed.is_empty() {
+            let _ = writeln!(out, "## フォルダの説明ファイル\n");
+            for note in changed {
+                let _ = writeln!(out, "- `{}`（{}）", note.path.display(), note.status.label());
+            }
+            out.push('\n');
+        }
+
+        if self.is_complete() && self.category_issues.is_empty() {
+            let _ = writeln!(out, "すべての標準フォルダが揃っています。");
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(line: &str) -> (String, String, CategoryRule) {
+        let (_, lines) = StructureManager::parse_tree(line).unwrap();
+        let line = lines.into_iter().next().unwrap();
+        let (name, rule) = line.template.expect("テンプレートとして読めること");
+        (line.path, name, rule)
+    }
+
+    #[test]
+    fn parses_any_template() {
+        let (path, name, rule) = template("1_projects/[category]");
+        assert_eq!((path.as_str(), name.as_str()), ("1_projects", "category"));
+        assert!(matches!(rule, CategoryRule::Any));
+    }
+
+    #[test]
+    fn parses_names_template() {
+        let (path, name, rule) = template("2_assets/[kind: footage, audio] # 素材の種類");
+        assert_eq!((path.as_str(), name.as_str()), ("2_assets", "kind"));
+        let CategoryRule::Names(names) = rule else {
+            panic!("名前の一覧として読めること");
+        };
+        assert_eq!(names, vec!["footage".to_string(), "audio".to_string()]);
+    }
+
+    #[test]
+    fn parses_pattern_template_with_brackets() {
+        let (path, name, rule) = template("1_projects/[category: /^[a-z]+$/]");
+        assert_eq!((path.as_str(), name.as_str()), ("1_projects", "category"));
+        let CategoryRule::Pattern(pattern) = rule else {
+            panic!("正規表現として読めること");
+        };
+        assert!(pattern.is_match("film"));
+        assert!(!pattern.is_match("Film"));
+
+        let (path, _, rule) = template("3_docs/notes/[topic: /^[^\\]/]+$/]");
+        assert_eq!(path, "3_docs/notes");
+        assert!(matches!(rule, CategoryRule::Pattern(_)));
+    }
+
+    #[test]
+    fn nested_template_lines_use_the_parent_path() {
+        let (_, lines) = StructureManager::parse_tree("1_projects\n  [category: /^[a-z]+$/]\n").unwrap();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].path, "1_projects");
+        assert!(lines[1].template.is_some());
+    }
+
+    #[test]
+    fn markdown_lists_missing_folders_with_descriptions() {
+        let definition = StructureDefinition {
+            profile: "default".to_string(),
+            entries: vec![StructureEntry {
+                path: "3_docs".to_string(),
+                description: Some("資料".to_string()),
+            }],
+            templates: Vec::new(),
+        };
+        let result = StructureValidationResult {
+            root: PathBuf::from("/drive"),
+            profile: "default".to_string(),
+            total: 1,
+            existing: Vec::new(),
+            missing: vec!["3_docs".to_string()],
+            category_issues: Vec::new(),
+        };
+
+        let markdown = result.to_markdown(&definition, &[]);
+        assert!(markdown.starts_with("# 標準フォルダ構造の確認"));
+        assert!(markdown.contains("完成度: 0.0%（0 / 1）"));
+        assert!(markdown.contains("- `3_docs` — 資料"));
+    }
+
+    #[test]
+    fn plain_folders_are_not_templates() {
+        let (_, lines) = StructureManager::parse_tree("2_assets/footage\n").unwrap();
+        assert_eq!(lines[0].path, "2_assets/footage");
+        assert!(lines[0].template.is_none());
+    }
 }
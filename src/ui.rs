@@ -1,9 +1,21 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use colored::*;
 use indicatif::{ProgressBar, ProgressStyle};
 
+use crate::asset_audit::AssetAuditReport;
+use crate::disk_usage::UsageReport;
+use crate::download_manager::DownloadPlan;
+use crate::folder_note::{FolderNote, FolderNoteStatus};
+use crate::gallery_manager::{GalleryPlan, LinkMigration};
+use crate::health_report::HealthReport;
+use crate::routing_rules::{Route, RuleMatch, RuleTrace};
+use crate::scanner::FileInfo;
+use crate::structure_drift::{DriftMergeMode, DriftReport};
+use crate::structure_manager::{StructureDefinition, StructureValidationResult};
+use crate::project_archive::{ArchiveManifest, ArchivePlan};
+use crate::project_audit::ProjectAuditReport;
 use crate::record_manager::{ActionType, RecordFileAction, RecordOrganizationPlan};
 
 pub struct UI;
@@ -108,6 +120,606 @@ impl UI {
         }
     }
 
+    pub fn render_download_plan(plan: &DownloadPlan, verbose: bool) {
+        println!(
+            "{} {}",
+            "downloadsフォルダ:".bright_cyan().bold(),
+            plan.downloads_dir.display()
+        );
+        println!(
+            "{} {}",
+            "作成が必要なフォルダ:".bright_cyan(),
+            plan.moves.required_folders.len()
+        );
+        if !plan.moves.required_folders.is_empty() {
+            let folders = plan
+                .moves
+                .required_folders
+                .iter()
+                .map(|p| format!("📁 {}", Self::format_path(p)));
+            Self::preview_lines(folders, verbose);
+        }
+        println!(
+            "{} {}",
+            "ファイル操作数:".bright_cyan(),
+            plan.moves.actions.len()
+        );
+        if !plan.moves.actions.is_empty() {
+            let ops = plan.moves.actions.iter().map(|action| {
+                format!(
+                    "{} {}",
+                    Self::action_icon(action),
+                    Self::format_action(action)
+                )
+            });
+            Self::preview_lines(ops, verbose);
+        }
+        println!("{} {}", "確認が必要なもの:".bright_cyan(), plan.notes.len());
+        if !plan.notes.is_empty() {
+            let lines = plan.notes.iter().map(|note| {
+                format!(
+                    "⚠ [{}] {} {}",
+                    note.kind.label().bright_yellow(),
+                    Self::format_path(&note.path),
+                    note.detail.bright_black()
+                )
+            });
+            Self::preview_lines(lines, verbose);
+        }
+    }
+
+    pub fn render_rule_test(
+        rules_path: &Path,
+        file: &FileInfo,
+        traces: &[RuleTrace],
+        matched: Option<&RuleMatch>,
+    ) {
+        println!("{} {}", "ルールファイル:".bright_cyan().bold(), rules_path.display());
+        println!("{} {}", "ファイル:".bright_cyan(), file.path.display());
+        println!(
+            "  {} / {} / 更新 {}",
+            if file.extension.is_empty() { "-" } else { &file.extension },
+            Self::format_size(file.size),
+            file.modified.format("%Y-%m-%d %H:%M")
+        );
+
+        if traces.is_empty() {
+            println!("{}", "ルールがありません。".bright_black());
+        }
+        for trace in traces {
+            match &trace.mismatch {
+                Some(reason) => println!(
+                    "  {} {} {}",
+                    "✗".bright_black(),
+                    trace.rule,
+                    reason.bright_black()
+                ),
+                None => println!("  {} {}", "✓".bright_green(), trace.rule.bright_green()),
+            }
+        }
+
+        match matched {
+            Some(RuleMatch {
+                rule,
+                route: Route::Move { folder, file_name },
+            }) => println!(
+                "\n{} {} → {}",
+                "一致:".bright_green().bold(),
+                rule,
+                folder.join(file_name).display()
+            ),
+            Some(RuleMatch {
+                rule,
+                route: Route::Keep,
+            }) => println!(
+                "\n{} {} → その場に残します",
+                "一致:".bright_green().bold(),
+                rule
+            ),
+            None => println!(
+                "\n{}",
+                "どのルールにも一致しません（既定の振り分けを使います）。".bright_yellow()
+            ),
+        }
+    }
+
+    pub fn render_gallery_plan(plan: &GalleryPlan, verbose: bool) {
+        println!(
+            "{} {}",
+            "ギャラリー:".bright_cyan().bold(),
+            plan.gallery_dir.display()
+        );
+        println!(
+            "{} {}",
+            "探索したファイル数:".bright_cyan(),
+            plan.scanned_files
+        );
+        println!("{} {}", "対象の成果物:".bright_cyan(), plan.items.len());
+        if !plan.items.is_empty() {
+            let lines = plan.items.iter().map(|item| {
+                let score = format!("{:.2}", item.score);
+                let score = if item.score >= 1.0 {
+                    score.bright_green()
+                } else {
+                    score.bright_yellow()
+                };
+                let name = item.entries.first().unwrap_or(&item.key);
+                let mut notes = String::new();
+                if item.entries.len() > 1 {
+                    notes.push_str(&format!(" (+{} ビュー)", item.entries.len() - 1));
+                }
+                for tag in &item.tags {
+                    notes.push_str(&format!(" #{}", tag));
+                }
+                format!(
+                    "⇢ [{}] {}{} → {}",
+                    score,
+                    name,
+                    notes.bright_black(),
+                    Self::format_path(&item.target)
+                )
+            });
+            Self::preview_lines(lines, verbose);
+        }
+    }
+
+    pub fn render_project_audit(report: &ProjectAuditReport, verbose: bool) {
+        println!(
+            "{} {}",
+            "プロジェクトフォルダ:".bright_cyan().bold(),
+            report.projects_dir.display()
+        );
+        println!(
+            "{} {} / {} {}",
+            "カテゴリ数:".bright_cyan(),
+            report.categories,
+            "プロジェクト数:".bright_cyan(),
+            report.projects
+        );
+        println!("{} {}", "問題:".bright_cyan(), report.issues.len());
+        if !report.issues.is_empty() {
+            let lines = report.issues.iter().map(|issue| {
+                format!(
+                    "⚠ [{}] {} {}",
+                    issue.kind.label().bright_yellow(),
+                    Self::format_path(&issue.path),
+                    issue.detail.bright_black()
+                )
+            });
+            Self::preview_lines(lines, verbose);
+        }
+        println!("{} {}", "名前の変更案:".bright_cyan(), report.renames.len());
+        if !report.renames.is_empty() {
+            let lines = report.renames.iter().map(|rename| {
+                format!(
+                    "✎ {} → {}",
+                    Self::format_path(&rename.source),
+                    Self::format_path(&rename.target)
+                )
+            });
+            Self::preview_lines(lines, verbose);
+        }
+    }
+
+    pub fn render_asset_audit(report: &AssetAuditReport, verbose: bool) {
+        println!(
+            "{} {}",
+            "素材フォルダ:".bright_cyan().bold(),
+            report.assets_dir.display()
+        );
+        println!("{} {}", "ファイル数:".bright_cyan(), report.files);
+        println!("{} {}", "問題:".bright_cyan(), report.issues.len());
+        if !report.issues.is_empty() {
+            let lines = report.issues.iter().map(|issue| {
+                format!(
+                    "⚠ [{}] {} {}",
+                    issue.kind.label().bright_yellow(),
+                    Self::format_path(&issue.path),
+                    issue.detail.bright_black()
+                )
+            });
+            Self::preview_lines(lines, verbose);
+        }
+        println!(
+            "{} {}",
+            "作成が必要なフォルダ:".bright_cyan(),
+            report.moves.required_folders.len()
+        );
+        if !report.moves.required_folders.is_empty() {
+            let folders = report
+                .moves
+                .required_folders
+                .iter()
+                .map(|p| format!("📁 {}", Self::format_path(p)));
+            Self::preview_lines(folders, verbose);
+        }
+        println!("{} {}", "移動案:".bright_cyan(), report.moves.actions.len());
+        if !report.moves.actions.is_empty() {
+            let ops = report.moves.actions.iter().map(|action| {
+                format!(
+                    "{} {}",
+                    Self::action_icon(action),
+                    Self::format_action(action)
+                )
+            });
+            Self::preview_lines(ops, verbose);
+        }
+    }
+
+    pub fn render_structure_drift(report: &DriftReport, verbose: bool) {
+        println!(
+            "{} {}（プロファイル: {}）",
+            "ルートディレクトリ:".bright_cyan().bold(),
+            report.root.display(),
+            report.profile
+        );
+        println!("{} {}", "確認した階層:".bright_cyan(), report.levels);
+        println!("{} {}", "想定外のエントリ:".bright_cyan(), report.entries.len());
+        if !report.entries.is_empty() {
+            let lines = report.entries.iter().map(|entry| {
+                let icon = if entry.is_dir { "📁" } else { "📄" };
+                match &entry.suggestion {
+                    Some((standard, reason)) => format!(
+                        "{} {} {}",
+                        icon,
+                        Self::format_path(&entry.path),
+                        format!(
+                            "→ {}（{}{}）",
+                            standard,
+                            reason.label(),
+                            if reason.can_merge() { "" } else { "・候補のみ" }
+                        )
+                        .bright_yellow()
+                    ),
+                    None => format!("{} {}", icon, Self::format_path(&entry.path)),
+                }
+            });
+            Self::preview_lines(lines, verbose);
+        }
+        println!("{} {}", "まとめる計画:".bright_cyan(), report.merges.len());
+        if !report.merges.is_empty() {
+            let lines = report.merges.iter().map(|merge| {
+                let label = match merge.mode {
+                    DriftMergeMode::Rename => "名前を変更",
+                    DriftMergeMode::Merge => "中身をまとめる",
+                };
+                format!(
+                    "✎ {} → {} {}",
+                    Self::format_path(&merge.source),
+                    Self::format_path(&merge.target),
+                    format!("（{}）", label).bright_black()
+                )
+            });
+            Self::preview_lines(lines, verbose);
+        }
+    }
+
+    pub fn render_structure_check(
+        result: &StructureValidationResult,
+        definition: &StructureDefinition,
+        verbose: bool,
+    ) {
+        println!(
+            "{} {}（プロファイル: {}）",
+            "ルートディレクトリ:".bright_cyan().bold(),
+            result.root.display(),
+            result.profile
+        );
+        println!(
+            "{} {:.1}%（{} / {}）",
+            "完成度:".bright_cyan(),
+            result.completion_rate(),
+            result.existing.len(),
+            result.total
+        );
+        if !result.category_issues.is_empty() {
+            println!(
+                "{} {}",
+                "カテゴリの問題:".bright_cyan(),
+                result.category_issues.len()
+            );
+            let lines = result.category_issues.iter().map(|issue| {
+                format!(
+                    "✗ {} {}",
+                    Self::format_path(&issue.path),
+                    format!("（{}: {}）", issue.kind.label(), issue.template).bright_yellow()
+                )
+            });
+            Self::preview_lines(lines, verbose);
+        }
+        if result.missing.is_empty() {
+            return;
+        }
+        println!("{} {}", "不足しているフォルダ:".bright_cyan(), result.missing.len());
+        let lines = result.missing.iter().map(|path| {
+            let description = definition
+                .entries
+                .iter()
+                .find(|entry| &entry.path == path)
+                .and_then(|entry| entry.description.as_deref());
+            match description {
+                Some(description) => {
+                    format!("✗ {} {}", path, format!("# {}", description).bright_black())
+                }
+                None => format!("✗ {}", path),
+            }
+        });
+        Self::preview_lines(lines, verbose);
+    }
+
+    pub fn render_folder_notes(notes: &[FolderNote], verbose: bool) {
+        if notes.is_empty() {
+            return;
+        }
+        let count = |status: FolderNoteStatus| {
+            notes.iter().filter(|note| note.status == status).count()
+        };
+        println!(
+            "{} 作成 {} / 更新 {} / 変更なし {} / 手元の編集を保持 {}",
+            "フォルダの説明ファイル:".bright_cyan(),
+            count(FolderNoteStatus::Created),
+            count(FolderNoteStatus::Updated),
+            count(FolderNoteStatus::Unchanged),
+            count(FolderNoteStatus::Edited)
+        );
+        let lines = notes
+            .iter()
+            .filter(|note| note.status != FolderNoteStatus::Unchanged)
+            .map(|note| {
+                format!(
+                    "✎ {} {}",
+                    Self::format_path(&note.path),
+                    format!("（{}）", note.status.label()).bright_black()
+                )
+            });
+        Self::preview_lines(lines, verbose);
+    }
+
+    pub fn render_health_report(report: &HealthReport, verbose: bool) {
+        println!(
+            "{} {}（{}）",
+            "ルートディレクトリ:".bright_cyan().bold(),
+            report.root.display(),
+            report.generated_at
+        );
+
+        println!("\n{}", "■ 標準フォルダ構造".bright_cyan().bold());
+        let structure = &report.structure;
+        println!(
+            "  完成度 {:.1}%（プロファイル: {}）",
+            structure.completion_rate, structure.profile
+        );
+        println!(
+            "  不足しているフォルダ {} / カテゴリの問題 {}",
+            structure.missing.len(),
+            structure.category_issues
+        );
+        if !structure.missing.is_empty() {
+            Self::preview_lines(
+                structure.missing.iter().map(|folder| format!("  ✗ {}", folder)),
+                verbose,
+            );
+        }
+
+        println!("\n{}", "■ 未整理のファイル".bright_cyan().bold());
+        match &report.downloads {
+            Some(downloads) => {
+                println!("  downloads:");
+                for kind in &downloads.kinds {
+                    println!("  {:>6}  {}", kind.count, kind.kind.label());
+                }
+                println!("  {:>6}  フォルダ", downloads.folders);
+                if let Some(oldest) = &downloads.oldest {
+                    println!(
+                        "  最も古いダウンロード: {} {}",
+                        Self::format_path(&oldest.path),
+                        format!("（{} 日前）", oldest.days).bright_yellow()
+                    );
+                }
+            }
+            None => println!("  {}", "downloads フォルダがありません".bright_black()),
+        }
+        match &report.records {
+            Some(records) => {
+                println!("  record:");
+                println!("  {:>6}  種別フォルダ外", records.unsorted);
+                println!("  {:>6}  命名規則違反", records.misnamed);
+                println!("  {:>6}  種別の取り違え", records.misplaced);
+            }
+            None => println!("  {}", "record フォルダがありません".bright_black()),
+        }
+
+        println!("\n{}", "■ プロジェクトとギャラリー".bright_cyan().bold());
+        match &report.gallery {
+            Some(gallery) => {
+                println!("  {:>6}  プロジェクト", gallery.projects);
+                println!(
+                    "  {:>6}  成果物の無いプロジェクト",
+                    gallery.projects_without_outputs.len()
+                );
+                Self::preview_lines(
+                    gallery
+                        .projects_without_outputs
+                        .iter()
+                        .map(|project| format!("          {}", Self::format_path(project))),
+                    verbose,
+                );
+                println!("  {:>6}  リンク切れ", gallery.dangling_links.len());
+                Self::preview_lines(
+                    gallery.dangling_links.iter().map(|link| {
+                        format!(
+                            "          {} {}",
+                            Self::format_path(&link.link),
+                            format!("→ {}", Self::format_path(&link.target)).bright_black()
+                        )
+                    }),
+                    verbose,
+                );
+            }
+            None => println!("  {}", "1_projects フォルダがありません".bright_black()),
+        }
+
+        println!("\n{}", "■ フォルダごとのサイズ".bright_cyan().bold());
+        for size in &report.sizes {
+            println!(
+                "  {:>10}  {:>8} 件  {}",
+                Self::format_size(size.bytes),
+                size.files,
+                size.folder
+            );
+        }
+    }
+
+    pub fn render_disk_usage(report: &UsageReport, verbose: bool) {
+        println!(
+            "{} {}",
+            "ルートディレクトリ:".bright_cyan().bold(),
+            report.root.display()
+        );
+        println!(
+            "{} {} 件 / {}",
+            "合計:".bright_cyan(),
+            report.files,
+            Self::format_size(report.bytes)
+        );
+
+        let row = |bytes: u64, files: usize, name: &str| {
+            format!("  {:>10}  {:>8} 件  {}", Self::format_size(bytes), files, name)
+        };
+
+        println!("\n{}", "■ 標準フォルダ".bright_cyan().bold());
+        for folder in &report.folders {
+            let depth = folder.name.matches('/').count();
+            let name = folder.name.rsplit('/').next().unwrap_or(&folder.name);
+            println!("{}", row(folder.bytes, folder.files, &format!("{}{}", "  ".repeat(depth), name)));
+        }
+
+        if !report.records.is_empty() {
+            println!("\n{}", "■ record（種別・月）".bright_cyan().bold());
+            let lines = report.records.iter().map(|record| {
+                row(
+                    record.bytes,
+                    record.files,
+                    &format!("{} / {}", record.record_type, record.month),
+                )
+            });
+            Self::preview_lines(lines, verbose);
+        }
+
+        if !report.projects.is_empty() {
+            println!("\n{}", "■ プロジェクト".bright_cyan().bold());
+            let lines = report
+                .projects
+                .iter()
+                .map(|project| row(project.bytes, project.files, &project.name));
+            Self::preview_lines(lines, verbose);
+        }
+
+        if !report.largest.is_empty() {
+            println!("\n{}", "■ 大きいファイル".bright_cyan().bold());
+            for file in &report.largest {
+                println!(
+                    "  {:>10}  {}  {}",
+                    Self::format_size(file.bytes),
+                    file.modified.bright_black(),
+                    Self::format_path(&file.path)
+                );
+            }
+        }
+    }
+
+    pub fn render_archive_plan(plan: &ArchivePlan, verbose: bool) {
+        println!(
+            "{} {}",
+            "アーカイブ先:".bright_cyan().bold(),
+            plan.archive_dir.display()
+        );
+        let total: u64 = plan.ready().map(|item| item.size).sum();
+        println!(
+            "{} {}（{}）",
+            "対象のプロジェクト:".bright_cyan(),
+            plan.ready().count(),
+            Self::format_size(total)
+        );
+        if !plan.items.is_empty() {
+            let lines = plan.items.iter().map(|item| {
+                let name = format!("{}/{}", item.category, item.name);
+                match &item.skip_reason {
+                    Some(reason) => format!("✗ {} {}", name, reason.bright_yellow()),
+                    None => {
+                        let modified = item
+                            .last_modified
+                            .map(|time| {
+                                chrono::DateTime::<chrono::Local>::from(time)
+                                    .format("%Y-%m-%d")
+                                    .to_string()
+                            })
+                            .unwrap_or_else(|| "-".to_string());
+                        format!(
+                            "⇢ {} {} → {}",
+                            name,
+                            format!(
+                                "({} ファイル, {}, 最終更新 {})",
+                                item.files,
+                                Self::format_size(item.size),
+                                modified
+                            )
+                            .bright_black(),
+                            Self::format_path(&item.archive_path)
+                        )
+                    }
+                }
+            });
+            Self::preview_lines(lines, verbose);
+        }
+    }
+
+    pub fn render_archive_list(archives: &[(PathBuf, ArchiveManifest)], verbose: bool) {
+        println!("{} {}", "アーカイブ:".bright_cyan().bold(), archives.len());
+        if !archives.is_empty() {
+            let lines = archives.iter().map(|(path, manifest)| {
+                let size: u64 = manifest.files.iter().map(|file| file.size).sum();
+                let archived_at = chrono::DateTime::parse_from_rfc3339(&manifest.archived_at)
+                    .map(|time| time.format("%Y-%m-%d").to_string())
+                    .unwrap_or_else(|_| manifest.archived_at.clone());
+                format!(
+                    "📦 {}/{} {}\n     元の場所: {}\n     {}",
+                    manifest.category,
+                    manifest.project,
+                    format!(
+                        "({} ファイル, {}, アーカイブ日 {})",
+                        manifest.files.len(),
+                        Self::format_size(size),
+                        archived_at
+                    )
+                    .bright_black(),
+                    manifest.original_path,
+                    Self::format_path(path).bright_black()
+                )
+            });
+            Self::preview_lines(lines, verbose);
+        }
+    }
+
+    pub fn render_link_migrations(migrations: &[LinkMigration], verbose: bool) {
+        println!(
+            "{} {}",
+            "相対パスに変換するリンク:".bright_cyan(),
+            migrations.len()
+        );
+        if !migrations.is_empty() {
+            let lines = migrations.iter().map(|migration| {
+                format!(
+                    "⇢ {}: {} → {}",
+                    Self::format_path(&migration.link),
+                    Self::format_path(&migration.old_target),
+                    Self::format_path(&migration.new_target)
+                )
+            });
+            Self::preview_lines(lines, verbose);
+        }
+    }
+
     fn preview_lines<I>(lines: I, verbose: bool)
     where
         I: Iterator<Item = String>,
@@ -142,6 +754,22 @@ impl UI {
         }
     }
 
+    /// バイト数を KB / MB / GB 表記にする
+    pub fn format_size(bytes: u64) -> String {
+        const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+        let mut size = bytes as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+        if unit == 0 {
+            format!("{} {}", bytes, UNITS[0])
+        } else {
+            format!("{:.1} {}", size, UNITS[unit])
+        }
+    }
+
     fn format_path(path: &Path) -> String {
         path.to_string_lossy().to_string()
     }